@@ -0,0 +1,127 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![forbid(unsafe_code)]
+
+//!
+//! Derive macros for the polymorph crate. These are re-exported by polymorph
+//! when its "derive" feature is enabled.
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, Index, Type};
+
+/// Generates a `reborrow` method, which lends out a view of the struct whose
+/// wrappers all borrow from it.
+///
+/// The struct must have exactly one lifetime parameter. Fields of type `RefOrOwned`
+/// or `RefOrBox` become the Borrowed variant, pointing to the wrapped value of the
+/// original field. All other fields are cloned. Mutable wrappers cannot be lent out
+/// from a shared reference, and are rejected.
+///
+/// ```rust
+/// use polymorph::Reborrow;
+/// use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+///
+/// #[derive(Reborrow)]
+/// struct Document<'t> {
+///     title: RefOrOwned<'t, String>,
+///     body: RefOrBox<'t, str>,
+///     revision: u32
+/// }
+///
+/// let document = Document {
+///     title: RefOrOwned::Owned(String::from("Title")),
+///     body: RefOrBox::Owned(Box::from("Body")),
+///     revision: 1
+/// };
+/// let view = document.reborrow();
+/// assert!(matches!(view.title, RefOrOwned::Borrowed(_)));
+/// assert!(matches!(view.body, RefOrBox::Borrowed("Body")));
+/// ```
+#[proc_macro_derive(Reborrow)]
+pub fn derive_reborrow(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_reborrow(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand_reborrow(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if input.generics.lifetimes().count() != 1 {
+        return Err(Error::new(input.generics.span(), "Reborrow requires exactly one lifetime parameter"));
+    }
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => return Err(Error::new(name.span(), "Reborrow can only be derived for structs"))
+    };
+    let body = match &data.fields {
+        Fields::Named(fields) => {
+            let initializers = fields.named.iter().map(|field| {
+                let ident = &field.ident;
+                let value = reborrow_field(quote!(#ident), &field.ty)?;
+                Ok(quote!(#ident: #value))
+            }).collect::<syn::Result<Vec<_>>>()?;
+            quote!(#name { #(#initializers),* })
+        },
+        Fields::Unnamed(fields) => {
+            let values = fields.unnamed.iter().enumerate().map(|(index, field)| {
+                let index = Index::from(index);
+                reborrow_field(quote!(#index), &field.ty)
+            }).collect::<syn::Result<Vec<_>>>()?;
+            quote!(#name(#(#values),*))
+        },
+        Fields::Unit => quote!(#name)
+    };
+    let view_arguments = input.generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(_) => quote!('_),
+        GenericParam::Type(type_param) => {
+            let ident = &type_param.ident;
+            quote!(#ident)
+        },
+        GenericParam::Const(const_param) => {
+            let ident = &const_param.ident;
+            quote!(#ident)
+        }
+    });
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            /// Lends out a view of this value whose wrappers borrow from it
+            pub fn reborrow(&self) -> #name<#(#view_arguments),*> {
+                #body
+            }
+        }
+    })
+}
+
+fn reborrow_field(access: TokenStream2, ty: &Type) -> syn::Result<TokenStream2> {
+    let wrapper = match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None
+    };
+    match wrapper.as_deref() {
+        Some("RefOrOwned") | Some("RefOrBox") => {
+            Ok(quote!(::core::convert::From::from(&*self.#access)))
+        },
+        Some("RefMutOrOwned") | Some("RefMutOrBox") => {
+            Err(Error::new(ty.span(), "Mutable wrappers cannot be reborrowed from a shared reference"))
+        },
+        _ => Ok(quote!(::core::clone::Clone::clone(&self.#access)))
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+
+/// A value which is either already available, or is computed by a future upon first access.
+///
+/// `AsyncLazyOrOwned` lets an API accept either a ready value or a deferred computation,
+/// such as a fetch from a remote service, under the same type. The future is run at most
+/// once; its output is then cached in the Owned variant.
+///
+/// ```rust
+/// # use polymorph::async_lazy::AsyncLazyOrOwned;
+/// async fn describe(mut config: AsyncLazyOrOwned<'_, String>) -> usize {
+///     config.get().await.len()
+/// }
+///
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     struct NoopWaker;
+/// #     impl std::task::Wake for NoopWaker {
+/// #         fn wake(self: std::sync::Arc<Self>) {}
+/// #     }
+/// #     let waker = std::task::Waker::from(std::sync::Arc::new(NoopWaker));
+/// #     let mut context = std::task::Context::from_waker(&waker);
+/// #     let mut future = Box::pin(future);
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// let ready = describe(AsyncLazyOrOwned::from(String::from("ready")));
+/// let deferred = describe(AsyncLazyOrOwned::pending(async { String::from("fetched") }));
+/// assert_eq!(5, block_on(ready));
+/// assert_eq!(7, block_on(deferred));
+/// ```
+pub enum AsyncLazyOrOwned<'t, T> {
+    Pending(Pin<Box<dyn Future<Output = T> + Send + 't>>),
+    Owned(T)
+}
+
+impl<'t, T> AsyncLazyOrOwned<'t, T> {
+    /// Defers the value to the given future, which is run upon first access
+    pub fn pending<F>(future: F) -> Self where F: Future<Output = T> + Send + 't {
+        Self::Pending(Box::pin(future))
+    }
+
+    /// Whether the value has already been computed or provided
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+
+    /// Obtains a reference to the value, running the future first if it is pending.
+    ///
+    /// If the returned future is dropped before completion, progress on the pending
+    /// future is kept, and a subsequent call resumes it.
+    pub async fn get(&mut self) -> &T {
+        self.get_mut().await
+    }
+
+    /// Obtains a mutable reference to the value, running the future first if it is pending.
+    ///
+    /// If the returned future is dropped before completion, progress on the pending
+    /// future is kept, and a subsequent call resumes it.
+    pub async fn get_mut(&mut self) -> &mut T {
+        if let Self::Pending(pending) = self {
+            let value = pending.as_mut().await;
+            *self = Self::Owned(value);
+        }
+        match self {
+            Self::Pending(_) => unreachable!(),
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+
+    /// Obtains the value, running the future first if it is pending
+    pub async fn into_owned(self) -> T {
+        match self {
+            Self::Pending(pending) => pending.await,
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl<T> From<T> for AsyncLazyOrOwned<'_, T> {
+    fn from(value: T) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<T: Default> Default for AsyncLazyOrOwned<'_, T> {
+    fn default() -> Self {
+        Self::Owned(T::default())
+    }
+}
+
+impl<T: Debug> Debug for AsyncLazyOrOwned<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending(_) => f.write_str("Pending"),
+            Self::Owned(owned_value) => f.debug_tuple("Owned").field(owned_value).finish()
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "async_lazy_tests.rs"]
+mod async_lazy_tests;
@@ -0,0 +1,87 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::async_lazy::AsyncLazyOrOwned;
+use crate::test_support::{block_on, poll_once};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::{Context, Poll};
+
+/// Completes on the second poll
+#[derive(Default)]
+struct YieldOnce {
+    yielded: bool
+}
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _context: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn ready_value() {
+    let mut lazy = AsyncLazyOrOwned::from(3);
+    assert!(lazy.is_ready());
+    assert_eq!(3, *block_on(lazy.get()));
+    assert_eq!(3, block_on(lazy.into_owned()));
+}
+
+#[test]
+fn pending_value_computed_once() {
+    let runs = AtomicU32::new(0);
+    let mut lazy = AsyncLazyOrOwned::pending(async {
+        runs.fetch_add(1, Ordering::Relaxed);
+        String::from("computed")
+    });
+    assert!(!lazy.is_ready());
+    assert_eq!("computed", block_on(lazy.get()));
+    block_on(lazy.get_mut()).push('!');
+    assert_eq!("computed!", block_on(lazy.into_owned()));
+    assert_eq!(1, runs.load(Ordering::Relaxed));
+}
+
+#[test]
+fn dropped_access_resumes() {
+    let runs = AtomicU32::new(0);
+    let mut lazy = AsyncLazyOrOwned::pending(async {
+        runs.fetch_add(1, Ordering::Relaxed);
+        YieldOnce::default().await;
+        7
+    });
+    {
+        let mut access = Box::pin(lazy.get());
+        assert!(poll_once(&mut access).is_pending());
+    }
+    assert!(!lazy.is_ready());
+    assert_eq!(7, *block_on(lazy.get()));
+    assert_eq!(1, runs.load(Ordering::Relaxed));
+}
+
+#[test]
+fn debug_format() {
+    let lazy: AsyncLazyOrOwned<u8> = AsyncLazyOrOwned::pending(async { 1 });
+    assert_eq!("Pending", format!("{:?}", lazy));
+    assert_eq!("Owned(1)", format!("{:?}", AsyncLazyOrOwned::from(1)));
+}
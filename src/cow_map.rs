@@ -0,0 +1,160 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use crate::ref_or_owned::RefOrOwned;
+
+/// A copy-on-write overlay over a borrowed `HashMap`.
+///
+/// The base map is borrowed and never modified. Insertions and removals are recorded
+/// in an owned delta, which lookups consult before the base map. A full map is only
+/// materialized by `into_owned`.
+///
+/// ```rust
+/// # use polymorph::cow_map::CowMap;
+/// use std::collections::HashMap;
+///
+/// let mut defaults = HashMap::new();
+/// defaults.insert("timeout", 30);
+/// defaults.insert("retries", 3);
+///
+/// let mut overrides = CowMap::new(&defaults);
+/// overrides.insert("timeout", 60);
+/// overrides.remove("retries");
+///
+/// assert_eq!(Some(&60), overrides.get("timeout"));
+/// assert_eq!(None, overrides.get("retries"));
+/// assert_eq!(Some(&30), defaults.get("timeout"));
+/// ```
+#[derive(Debug)]
+pub struct CowMap<'t, K, V> {
+    base: &'t HashMap<K, V>,
+    inserted: HashMap<K, V>,
+    removed: HashSet<K>
+}
+
+impl<'t, K, V> CowMap<'t, K, V> where K: Eq + Hash {
+    /// Creates an overlay without any changes to the base map
+    pub fn new(base: &'t HashMap<K, V>) -> Self {
+        Self {
+            base,
+            inserted: HashMap::new(),
+            removed: HashSet::new()
+        }
+    }
+
+    /// Whether any entries were inserted or removed
+    pub fn is_modified(&self) -> bool {
+        !self.inserted.is_empty() || !self.removed.is_empty()
+    }
+
+    /// Obtains the value for the given key, preferring inserted values to those
+    /// of the base map
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        if let Some(inserted_value) = self.inserted.get(key) {
+            return Some(inserted_value);
+        }
+        if self.removed.contains(key) {
+            return None;
+        }
+        self.base.get(key)
+    }
+
+    /// Whether a value is present for the given key
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        self.get(key).is_some()
+    }
+
+    /// Inserts a value into the overlay.
+    ///
+    /// Returns the previous value, if any. A previous value from the base map is
+    /// returned borrowed, and one from the overlay is returned owned.
+    pub fn insert(&mut self, key: K, value: V) -> Option<RefOrOwned<'t, V>> {
+        let base_value = if self.removed.remove(&key) {
+            None
+        } else {
+            self.base.get(&key)
+        };
+        match self.inserted.insert(key, value) {
+            Some(previous_value) => Some(RefOrOwned::Owned(previous_value)),
+            None => base_value.map(RefOrOwned::Borrowed)
+        }
+    }
+
+    /// Removes the value for the given key from the overlay.
+    ///
+    /// Returns the previous value, if any. A previous value from the base map is
+    /// returned borrowed, and one from the overlay is returned owned.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<RefOrOwned<'t, V>>
+        where K: Borrow<Q> + Clone, Q: ?Sized + Eq + Hash {
+
+        if let Some((inserted_key, inserted_value)) = self.inserted.remove_entry(key) {
+            if self.base.contains_key(key) {
+                self.removed.insert(inserted_key);
+            }
+            return Some(RefOrOwned::Owned(inserted_value));
+        }
+        if self.removed.contains(key) {
+            return None;
+        }
+        let (base_key, base_value) = self.base.get_key_value(key)?;
+        self.removed.insert(base_key.clone());
+        Some(RefOrOwned::Borrowed(base_value))
+    }
+
+    /// The number of entries visible through the overlay
+    pub fn len(&self) -> usize {
+        let added = self.inserted.keys().filter(|key| !self.base.contains_key(*key)).count();
+        self.base.len() - self.removed.len() + added
+    }
+
+    /// Whether no entries are visible through the overlay
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the entries visible through the overlay, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let base_entries = self.base.iter().filter(move |(key, _)| {
+            !self.inserted.contains_key(*key) && !self.removed.contains(*key)
+        });
+        self.inserted.iter().chain(base_entries)
+    }
+
+    /// Materializes the overlay into a full map.
+    ///
+    /// Entries of the base map which remain visible are cloned. Inserted entries are moved.
+    pub fn into_owned(self) -> HashMap<K, V> where K: Clone, V: Clone {
+        let Self { base, mut inserted, removed } = self;
+        inserted.reserve(base.len().saturating_sub(removed.len()));
+        for (key, value) in base {
+            if !removed.contains(key) && !inserted.contains_key(key) {
+                inserted.insert(key.clone(), value.clone());
+            }
+        }
+        inserted
+    }
+}
+
+#[cfg(test)]
+#[path = "cow_map_tests.rs"]
+mod cow_map_tests;
@@ -0,0 +1,84 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::cow_map::CowMap;
+use crate::ref_or_owned::RefOrOwned;
+use std::collections::HashMap;
+
+fn base() -> HashMap<String, u32> {
+    let mut base = HashMap::new();
+    base.insert(String::from("one"), 1);
+    base.insert(String::from("two"), 2);
+    base
+}
+
+#[test]
+fn unmodified_overlay() {
+    let base = base();
+    let overlay = CowMap::new(&base);
+    assert!(!overlay.is_modified());
+    assert_eq!(2, overlay.len());
+    assert_eq!(Some(&1), overlay.get("one"));
+    assert!(!overlay.contains_key("three"));
+}
+
+#[test]
+fn insert_shadows_base() {
+    let base = base();
+    let mut overlay = CowMap::new(&base);
+    assert!(matches!(overlay.insert(String::from("one"), 10), Some(RefOrOwned::Borrowed(1))));
+    assert!(matches!(overlay.insert(String::from("one"), 11), Some(RefOrOwned::Owned(10))));
+    assert!(overlay.insert(String::from("three"), 3).is_none());
+
+    assert_eq!(Some(&11), overlay.get("one"));
+    assert_eq!(3, overlay.len());
+    assert_eq!(Some(&1), base.get("one"));
+}
+
+#[test]
+fn remove_hides_base() {
+    let base = base();
+    let mut overlay = CowMap::new(&base);
+    assert!(matches!(overlay.remove("one"), Some(RefOrOwned::Borrowed(1))));
+    assert!(overlay.remove("one").is_none());
+    assert_eq!(1, overlay.len());
+
+    overlay.insert(String::from("one"), 5);
+    assert!(matches!(overlay.remove("one"), Some(RefOrOwned::Owned(5))));
+    assert!(overlay.get("one").is_none());
+
+    overlay.insert(String::from("new"), 6);
+    assert!(matches!(overlay.remove("new"), Some(RefOrOwned::Owned(6))));
+    assert_eq!(1, overlay.len());
+    assert!(overlay.is_modified());
+}
+
+#[test]
+fn iter_and_into_owned() {
+    let base = base();
+    let mut overlay = CowMap::new(&base);
+    overlay.insert(String::from("two"), 20);
+    overlay.insert(String::from("three"), 3);
+    overlay.remove("one");
+
+    let mut entries: Vec<(&String, &u32)> = overlay.iter().collect();
+    entries.sort();
+    assert_eq!(vec![(&String::from("three"), &3), (&String::from("two"), &20)], entries);
+
+    let owned = overlay.into_owned();
+    assert_eq!(2, owned.len());
+    assert_eq!(Some(&20), owned.get("two"));
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ref_or_owned::RefOrBox;
+
+type Hook<'t, T> = Box<dyn Fn(&T) + 't>;
+
+/// Wraps an inner object, which may be borrowed or owned, together with
+/// hooks which run before and after each call made through `call`.
+///
+/// Decorating an object never changes whether it is borrowed or owned. This
+/// allows instrumentation such as logging or timing to be added to any object,
+/// including trait objects, without taking ownership of it.
+///
+/// ```rust
+/// # use polymorph::decorator::Decorated;
+/// use std::cell::Cell;
+///
+/// trait Greeter {
+///     fn greet(&self) -> String;
+/// }
+///
+/// struct English;
+/// impl Greeter for English {
+///     fn greet(&self) -> String { String::from("Hello") }
+/// }
+///
+/// let calls = Cell::new(0);
+/// let greeter = English;
+/// let decorated = Decorated::new(&greeter as &dyn Greeter)
+///     .before(|_| calls.set(calls.get() + 1));
+///
+/// assert_eq!("Hello", decorated.call(|greeter| greeter.greet()));
+/// assert_eq!(1, calls.get());
+/// ```
+pub struct Decorated<'t, T: ?Sized + 't> {
+    inner: RefOrBox<'t, T>,
+    before: Vec<Hook<'t, T>>,
+    after: Vec<Hook<'t, T>>
+}
+
+impl<'t, T: ?Sized> Decorated<'t, T> {
+    /// Decorates the given object, initially without any hooks
+    pub fn new<I>(inner: I) -> Self where I: Into<RefOrBox<'t, T>> {
+        Self {
+            inner: inner.into(),
+            before: Vec::new(),
+            after: Vec::new()
+        }
+    }
+
+    /// Adds a hook which runs before each call. Hooks run in the order they are added.
+    pub fn before<F>(mut self, hook: F) -> Self where F: Fn(&T) + 't {
+        self.before.push(Box::new(hook));
+        self
+    }
+
+    /// Adds a hook which runs after each call. Hooks run in the order they are added.
+    pub fn after<F>(mut self, hook: F) -> Self where F: Fn(&T) + 't {
+        self.after.push(Box::new(hook));
+        self
+    }
+
+    /// Calls the given function on the inner object, running the hooks around it
+    pub fn call<R, F>(&self, function: F) -> R where F: FnOnce(&T) -> R {
+        let inner = &*self.inner;
+        self.before.iter().for_each(|hook| hook(inner));
+        let result = function(inner);
+        self.after.iter().for_each(|hook| hook(inner));
+        result
+    }
+
+    /// Accesses the inner object directly, without running any hooks
+    pub fn inner(&self) -> &RefOrBox<'t, T> {
+        &self.inner
+    }
+
+    /// Removes the hooks, yielding the inner object
+    pub fn into_inner(self) -> RefOrBox<'t, T> {
+        self.inner
+    }
+}
+
+#[cfg(test)]
+#[path = "decorator_tests.rs"]
+mod decorator_tests;
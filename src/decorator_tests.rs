@@ -0,0 +1,60 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::decorator::*;
+use crate::ref_or_owned::RefOrBox;
+use std::cell::RefCell;
+
+trait Adder {
+    fn add(&self, value: u32) -> u32;
+}
+
+struct ConstantAdder(u32);
+
+impl Adder for ConstantAdder {
+    fn add(&self, value: u32) -> u32 {
+        value + self.0
+    }
+}
+
+#[test]
+fn hooks_run_around_call() {
+    let events = RefCell::new(Vec::new());
+    let adder = ConstantAdder(2);
+    let decorated = Decorated::new(&adder as &dyn Adder)
+        .before(|_| events.borrow_mut().push("first before"))
+        .before(|_| events.borrow_mut().push("second before"))
+        .after(|_| events.borrow_mut().push("after"));
+
+    let result = decorated.call(|adder| {
+        events.borrow_mut().push("call");
+        adder.add(1)
+    });
+    assert_eq!(3, result);
+    assert_eq!(vec!["first before", "second before", "call", "after"], *events.borrow());
+}
+
+#[test]
+fn decorating_preserves_ownership() {
+    let adder = ConstantAdder(1);
+    let borrowed = Decorated::new(&adder as &dyn Adder).before(|_| {});
+    assert!(matches!(borrowed.into_inner(), RefOrBox::Borrowed(_)));
+
+    let owned: Box<dyn Adder> = Box::new(ConstantAdder(1));
+    let owned = Decorated::new(owned).after(|_| {});
+    assert_eq!(5, owned.inner().add(4));
+    assert!(matches!(owned.into_inner(), RefOrBox::Owned(_)));
+}
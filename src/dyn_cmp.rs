@@ -0,0 +1,218 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Object-safe counterparts of `Eq`, `PartialOrd`, `Ord`, and `Hash`.
+//!
+//! The standard comparison traits cannot be used as supertraits of a trait object,
+//! since they refer to `Self`. The traits in this module compare against `&dyn Any`
+//! instead, and are implemented for every type with the corresponding standard trait.
+//! Values of different concrete types are never equal.
+//!
+//! Making them supertraits allows the standard traits to be implemented for the trait
+//! object. Then, `RefOrBox` and `RefMutOrBox` holding the trait object implement the
+//! standard traits as well, so that they can be used as ordered or hashed keys.
+//! Implement them for `dyn Shape + '_` rather than `dyn Shape`, so that they also
+//! apply to wrappers borrowing for less than `'static`. The `impl_dyn_cmp!` macro
+//! generates these impls.
+//!
+//! ```rust
+//! # use polymorph::dyn_cmp::{AsAny, DynEq, DynHash};
+//! # use polymorph::ref_or_owned::RefOrBox;
+//! use std::collections::HashSet;
+//! use std::hash::{Hash, Hasher};
+//!
+//! trait Shape: DynEq + DynHash {}
+//!
+//! #[derive(PartialEq, Eq, Hash)]
+//! struct Circle { radius: u32 }
+//! impl Shape for Circle {}
+//!
+//! impl PartialEq for dyn Shape + '_ {
+//!     fn eq(&self, other: &Self) -> bool {
+//!         self.dyn_eq(other.as_any())
+//!     }
+//! }
+//!
+//! impl Eq for dyn Shape + '_ {}
+//!
+//! impl Hash for dyn Shape + '_ {
+//!     fn hash<H: Hasher>(&self, state: &mut H) {
+//!         self.dyn_hash(state)
+//!     }
+//! }
+//!
+//! let circle = Circle { radius: 1 };
+//! let mut shapes: HashSet<RefOrBox<dyn Shape>> = HashSet::new();
+//! shapes.insert(RefOrBox::Borrowed(&circle));
+//! assert!(!shapes.insert(RefOrBox::Owned(Box::new(Circle { radius: 1 }))));
+//! ```
+
+use std::any::{Any, TypeId};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Access to a value as `&dyn Any`. This is the common supertrait of the other
+/// traits in this module, and is implemented for all `'static` types.
+///
+/// Take care to call this on the trait object itself. Calling it on a reference
+/// or box which holds the trait object yields the reference or box instead.
+pub trait AsAny: Any {
+    /// Upcasts to `&dyn Any`
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// An object-safe counterpart of `Eq`.
+pub trait DynEq: AsAny {
+    /// Whether the other value has the same concrete type and is equal to this one
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: Eq + Any> DynEq for T {
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        match other.downcast_ref::<T>() {
+            Some(other) => self == other,
+            None => false
+        }
+    }
+}
+
+/// An object-safe counterpart of `PartialOrd`.
+pub trait DynPartialOrd: AsAny {
+    /// Compares with the other value, if it has the same concrete type. Values of
+    /// different types are unordered.
+    fn dyn_partial_cmp(&self, other: &dyn Any) -> Option<Ordering>;
+}
+
+impl<T: PartialOrd + Any> DynPartialOrd for T {
+    fn dyn_partial_cmp(&self, other: &dyn Any) -> Option<Ordering> {
+        other.downcast_ref::<T>().and_then(|other| self.partial_cmp(other))
+    }
+}
+
+/// An object-safe counterpart of `Ord`.
+pub trait DynOrd: DynEq {
+    /// Compares with the other value. Values of the same concrete type are compared
+    /// with `Ord`, while values of different types are ordered by their type IDs.
+    /// The latter order is consistent, but it may change between builds.
+    fn dyn_cmp(&self, other: &dyn Any) -> Ordering;
+}
+
+impl<T: Ord + Any> DynOrd for T {
+    fn dyn_cmp(&self, other: &dyn Any) -> Ordering {
+        match other.downcast_ref::<T>() {
+            Some(other) => self.cmp(other),
+            None => TypeId::of::<T>().cmp(&other.type_id())
+        }
+    }
+}
+
+/// An object-safe counterpart of `Hash`.
+pub trait DynHash: AsAny {
+    /// Feeds the concrete type and the value into the hasher
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T: Hash + Any> DynHash for T {
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<T>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
+/// Implements standard traits for a trait object, using the traits of this module.
+///
+/// The trait must have the corresponding traits of this module as supertraits. Each
+/// of the following may be listed after the trait:
+///
+/// * `eq`, for `PartialEq` and `Eq` using `DynEq`
+/// * `partial_ord`, for `PartialOrd` using `DynPartialOrd`
+/// * `ord`, for `PartialOrd` and `Ord` using `DynOrd`
+/// * `hash`, for `Hash` using `DynHash`
+///
+/// The impls apply to the trait object with any lifetime, so that wrappers which
+/// borrow for less than `'static` are covered.
+///
+/// ```rust
+/// # use polymorph::impl_dyn_cmp;
+/// # use polymorph::dyn_cmp::{DynHash, DynOrd};
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// use std::collections::BTreeSet;
+///
+/// trait Priority: DynOrd + DynHash {}
+/// impl Priority for u8 {}
+///
+/// impl_dyn_cmp!(Priority: eq, ord, hash);
+///
+/// let low = 1u8;
+/// let mut queue: BTreeSet<RefOrBox<dyn Priority>> = BTreeSet::new();
+/// queue.insert(RefOrBox::Owned(Box::new(3u8)));
+/// queue.insert(RefOrBox::Borrowed(&low));
+/// assert!(queue.first() == Some(&RefOrBox::Borrowed(&low as &dyn Priority)));
+/// ```
+#[macro_export]
+macro_rules! impl_dyn_cmp {
+    ($trait:path: $($cmp:ident),+) => {
+        $(
+        $crate::impl_dyn_cmp!(@$cmp $trait);
+        )+
+    };
+    (@eq $trait:path) => {
+        impl ::core::cmp::PartialEq for dyn $trait + '_ {
+            fn eq(&self, other: &Self) -> bool {
+                $crate::dyn_cmp::DynEq::dyn_eq(self, $crate::dyn_cmp::AsAny::as_any(other))
+            }
+        }
+
+        impl ::core::cmp::Eq for dyn $trait + '_ {}
+    };
+    (@partial_ord $trait:path) => {
+        impl ::core::cmp::PartialOrd for dyn $trait + '_ {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                $crate::dyn_cmp::DynPartialOrd::dyn_partial_cmp(self, $crate::dyn_cmp::AsAny::as_any(other))
+            }
+        }
+    };
+    (@ord $trait:path) => {
+        impl ::core::cmp::PartialOrd for dyn $trait + '_ {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for dyn $trait + '_ {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                $crate::dyn_cmp::DynOrd::dyn_cmp(self, $crate::dyn_cmp::AsAny::as_any(other))
+            }
+        }
+    };
+    (@hash $trait:path) => {
+        impl ::core::hash::Hash for dyn $trait + '_ {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                $crate::dyn_cmp::DynHash::dyn_hash(self, state)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+#[path = "dyn_cmp_tests.rs"]
+mod dyn_cmp_tests;
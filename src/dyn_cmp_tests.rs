@@ -0,0 +1,89 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::dyn_cmp::*;
+use crate::ref_or_owned::{RefMutOrBox, RefOrBox};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+
+trait Key: DynOrd + DynPartialOrd + DynHash {}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Number(u32);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Name(&'static str);
+
+impl Key for Number {}
+impl Key for Name {}
+
+impl_dyn_cmp!(Key: eq, ord, hash);
+
+trait Measure: DynEq + DynPartialOrd {}
+
+impl Measure for u32 {}
+impl Measure for Name {}
+
+impl_dyn_cmp!(Measure: eq, partial_ord);
+
+#[test]
+fn equality_requires_same_type() {
+    assert!(Number(1).dyn_eq(&Number(1)));
+    assert!(!Number(1).dyn_eq(&Number(2)));
+    assert!(!Number(1).dyn_eq(&Name("1")));
+    assert_eq!(Some(Ordering::Less), Number(1).dyn_partial_cmp(&Number(2)));
+    assert_eq!(None, Number(1).dyn_partial_cmp(&Name("1")));
+}
+
+#[test]
+fn ordering_groups_by_type() {
+    let first = Number(1).dyn_cmp(&Name("a"));
+    assert_ne!(Ordering::Equal, first);
+    assert_eq!(first.reverse(), Name("a").dyn_cmp(&Number(1)));
+    assert_eq!(Ordering::Greater, Name("b").dyn_cmp(&Name("a")));
+}
+
+#[test]
+fn wrappers_as_keys() {
+    let shared = Number(1);
+    let mut hashed: HashSet<RefOrBox<dyn Key>> = HashSet::new();
+    assert!(hashed.insert(RefOrBox::Borrowed(&shared)));
+    assert!(!hashed.insert(RefOrBox::Owned(Box::new(Number(1)))));
+    assert!(hashed.insert(RefOrBox::Owned(Box::new(Name("1")))));
+
+    let mut ordered: BTreeSet<RefOrBox<dyn Key>> = BTreeSet::new();
+    ordered.insert(RefOrBox::Owned(Box::new(Number(2))));
+    ordered.insert(RefOrBox::Borrowed(&shared));
+    ordered.insert(RefOrBox::Owned(Box::new(Number(1))));
+    assert_eq!(2, ordered.len());
+    let lowest: RefOrBox<dyn Key> = RefOrBox::Owned(Box::new(Number(1)));
+    assert!(ordered.first() == Some(&lowest));
+
+    let mut mutable = Number(3);
+    let first: RefMutOrBox<dyn Key> = RefMutOrBox::Borrowed(&mut mutable);
+    let second: RefMutOrBox<dyn Key> = RefMutOrBox::Owned(Box::new(Number(3)));
+    assert!(first == second);
+}
+
+#[test]
+fn partial_ord_macro() {
+    let first: RefOrBox<dyn Measure> = RefOrBox::Owned(Box::new(1u32));
+    let second: RefOrBox<dyn Measure> = RefOrBox::Owned(Box::new(2u32));
+    let name: RefOrBox<dyn Measure> = RefOrBox::Owned(Box::new(Name("1")));
+    assert!(first < second);
+    assert_eq!(None, first.partial_cmp(&name));
+    assert!(first != name);
+}
@@ -0,0 +1,189 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use crate::ref_or_owned::{ErrorChain, RefOrBox};
+
+/// The error trait object held by `PolyError`
+pub type DynError = dyn Error + Send + Sync + 'static;
+
+/// An error which is either a borrowed reference to an error, or a boxed error.
+///
+/// `RefOrBox<dyn Error>` cannot implement `Error` itself, because it would conflict with
+/// the blanket implementations of the standard library. This newtype does, by delegating
+/// to the wrapped error. Library APIs can thereby return errors which are sometimes
+/// borrowed static errors and sometimes boxed dynamic ones.
+///
+/// ```rust
+/// # use polymorph::error::PolyError;
+/// use std::error::Error;
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct NotFound;
+/// impl fmt::Display for NotFound {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("not found")
+///     }
+/// }
+/// impl Error for NotFound {}
+///
+/// static NOT_FOUND: NotFound = NotFound;
+///
+/// fn lookup(key: &str) -> Result<u32, PolyError<'static>> {
+///     match key.parse() {
+///         Ok(number) => Ok(number),
+///         Err(_) if key.is_empty() => Err(PolyError::from(&NOT_FOUND as &_)),
+///         Err(parse_error) => Err(PolyError::from_error(parse_error))
+///     }
+/// }
+///
+/// assert_eq!("not found", lookup("").unwrap_err().to_string());
+/// assert!(lookup("x").unwrap_err().is_owned());
+/// ```
+pub struct PolyError<'t>(pub RefOrBox<'t, DynError>);
+
+impl<'t> PolyError<'t> {
+    /// Boxes a concrete error, as the Owned variant. This may be passed to `map_err`.
+    pub fn from_error<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Self(RefOrBox::<DynError>::from_error(error))
+    }
+
+    /// Yields the wrapped error
+    pub fn into_inner(self) -> RefOrBox<'t, DynError> {
+        self.0
+    }
+
+    /// Iterates over the wrapped error and its chain of sources, starting with the
+    /// wrapped error itself.
+    ///
+    /// ```rust
+    /// # use polymorph::error::PolyError;
+    /// use std::error::Error;
+    /// use std::fmt;
+    /// use std::num::ParseIntError;
+    ///
+    /// #[derive(Debug)]
+    /// struct BadConfig(ParseIntError);
+    /// impl fmt::Display for BadConfig {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("bad config")
+    ///     }
+    /// }
+    /// impl Error for BadConfig {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let error = PolyError::from_error(BadConfig("x".parse::<u32>().unwrap_err()));
+    /// let messages: Vec<String> = error.sources().map(|error| error.to_string()).collect();
+    /// assert_eq!(vec!["bad config", "invalid digit found in string"], messages);
+    /// assert!(error.root_cause().is::<ParseIntError>());
+    /// ```
+    pub fn sources(&self) -> ErrorChain<'_> {
+        self.0.chain()
+    }
+
+    /// The last error in the chain of sources, which is the wrapped error itself if it
+    /// has no source.
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.0.root_cause()
+    }
+
+    /// Whether the error is borrowed
+    pub fn is_borrowed(&self) -> bool {
+        self.0.is_borrowed()
+    }
+
+    /// Whether the error is owned
+    pub fn is_owned(&self) -> bool {
+        self.0.is_owned()
+    }
+}
+
+impl<'t> From<RefOrBox<'t, DynError>> for PolyError<'t> {
+    fn from(value: RefOrBox<'t, DynError>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'t> From<&'t DynError> for PolyError<'t> {
+    fn from(value: &'t DynError) -> Self {
+        Self(RefOrBox::Borrowed(value))
+    }
+}
+
+impl From<Box<DynError>> for PolyError<'_> {
+    fn from(value: Box<DynError>) -> Self {
+        Self(RefOrBox::Owned(value))
+    }
+}
+
+impl<'t> From<PolyError<'t>> for RefOrBox<'t, DynError> {
+    fn from(value: PolyError<'t>) -> Self {
+        value.0
+    }
+}
+
+impl Deref for PolyError<'_> {
+    type Target = DynError;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
+
+impl AsRef<DynError> for PolyError<'_> {
+    #[inline]
+    fn as_ref(&self) -> &DynError {
+        self.deref()
+    }
+}
+
+impl Debug for PolyError<'_> {
+    /// Formats the wrapped error, as `Box<dyn Error>` does
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.deref(), f)
+    }
+}
+
+impl Display for PolyError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.deref(), f)
+    }
+}
+
+impl Error for PolyError<'_> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.deref().source()
+    }
+
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        self.deref().description()
+    }
+}
+
+#[cfg(feature = "anyhow")]
+#[path = "error_anyhow.rs"]
+mod error_anyhow;
+
+#[cfg(test)]
+#[path = "error_tests.rs"]
+mod error_tests;
@@ -0,0 +1,65 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between `PolyError` and `anyhow::Error`. This requires the "anyhow" feature.
+//!
+//! `anyhow::Error` converts from any error, including `PolyError<'static>`, so `?` already
+//! works in that direction. `into_anyhow` avoids the extra layer of wrapping, though.
+
+use super::{DynError, PolyError};
+use crate::ref_or_owned::RefOrBox;
+
+impl From<anyhow::Error> for PolyError<'_> {
+    /// Downgrades the error to a `Box<dyn Error + Send + Sync>`, as the Owned variant.
+    /// The chain of sources is kept.
+    fn from(value: anyhow::Error) -> Self {
+        Self(RefOrBox::Owned(value.into()))
+    }
+}
+
+impl From<anyhow::Error> for RefOrBox<'_, DynError> {
+    /// Downgrades the error to a `Box<dyn Error + Send + Sync>`, as the Owned variant.
+    fn from(value: anyhow::Error) -> Self {
+        Self::Owned(value.into())
+    }
+}
+
+impl PolyError<'static> {
+    /// Converts into an `anyhow::Error`. An owned error is handed over as its box, which
+    /// `downcast` can recover, whereas a borrowed error is wrapped in place.
+    ///
+    /// ```rust
+    /// # use polymorph::error::PolyError;
+    /// fn parse(text: &str) -> Result<u32, PolyError<'static>> {
+    ///     text.parse().map_err(PolyError::from_error)
+    /// }
+    ///
+    /// fn run() -> anyhow::Result<u32> {
+    ///     parse("x").map_err(PolyError::into_anyhow)
+    /// }
+    ///
+    /// let error = run().unwrap_err();
+    /// assert_eq!("invalid digit found in string", error.to_string());
+    /// let error = PolyError::from(error);
+    /// assert!(error.is_owned());
+    /// ```
+    pub fn into_anyhow(self) -> anyhow::Error {
+        match self.0 {
+            RefOrBox::Borrowed(_) => anyhow::Error::new(self),
+            RefOrBox::Owned(owned_value) => anyhow::Error::from_boxed(owned_value)
+        }
+    }
+}
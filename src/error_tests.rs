@@ -0,0 +1,121 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::error::*;
+use crate::ref_or_owned::RefOrBox;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+struct Outer {
+    cause: Inner
+}
+
+impl fmt::Display for Outer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl Error for Outer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.cause)
+    }
+}
+
+#[derive(Debug)]
+struct Inner;
+
+impl fmt::Display for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("inner")
+    }
+}
+
+impl Error for Inner {}
+
+static STATIC_ERROR: Outer = Outer { cause: Inner };
+
+fn fails(borrowed: bool) -> Result<(), Box<dyn Error>> {
+    if borrowed {
+        Err(PolyError::from(&STATIC_ERROR as &DynError))?
+    } else {
+        Err(PolyError::from(Box::new(Outer { cause: Inner }) as Box<DynError>))?
+    }
+}
+
+#[test]
+fn delegates_to_wrapped_error() {
+    for borrowed in [true, false] {
+        let error = fails(borrowed).unwrap_err();
+        assert_eq!("outer", error.to_string());
+        assert_eq!("Outer { cause: Inner }", format!("{:?}", error));
+        assert_eq!("inner", error.source().unwrap().to_string());
+        assert!(error.downcast_ref::<PolyError>().unwrap().is_borrowed() == borrowed);
+    }
+}
+
+#[test]
+fn convert_to_and_from_wrapper() {
+    let wrapper: RefOrBox<DynError> = RefOrBox::Borrowed(&STATIC_ERROR);
+    let error = PolyError::from(wrapper);
+    assert!(error.downcast_ref::<Outer>().is_some());
+    let wrapper: RefOrBox<DynError> = error.into();
+    assert!(wrapper.is_borrowed());
+}
+
+#[test]
+fn box_concrete_errors() {
+    fn parse(text: &str) -> Result<u32, PolyError<'static>> {
+        let number: u32 = text.parse().map_err(PolyError::from_error)?;
+        Ok(number)
+    }
+    assert_eq!(Ok(3), parse("3").map_err(|error| error.to_string()));
+    let error = parse("x").unwrap_err();
+    assert!(error.is_owned());
+    assert!(error.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    let wrapper = RefOrBox::<dyn Error + Send>::from_error(Inner);
+    assert!(wrapper.downcast_ref::<Inner>().is_some());
+}
+
+#[test]
+fn walk_sources() {
+    let error = PolyError::from(&STATIC_ERROR as &DynError);
+    let messages: Vec<String> = error.sources().map(|error| error.to_string()).collect();
+    assert_eq!(vec!["outer", "inner"], messages);
+    assert!(error.root_cause().is::<Inner>());
+
+    let error = PolyError::from_error(Inner);
+    assert_eq!(1, error.sources().count());
+    assert!(error.root_cause().is::<Inner>());
+}
+
+#[test]
+#[cfg(feature = "anyhow")]
+fn anyhow_round_trip() {
+    let error = PolyError::from_error(Inner).into_anyhow();
+    assert!(error.downcast_ref::<Box<DynError>>().unwrap().is::<Inner>());
+    let error = PolyError::from(error);
+    assert_eq!("inner", error.to_string());
+
+    let borrowed = PolyError::from(&STATIC_ERROR as &DynError).into_anyhow();
+    assert_eq!(vec!["outer", "inner"], borrowed.chain().map(|error| error.to_string()).collect::<Vec<_>>());
+
+    let wrapper: RefOrBox<DynError> = anyhow::anyhow!("message").into();
+    assert!(wrapper.is_owned());
+    assert_eq!("message", wrapper.to_string());
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::ref_or_owned::RefOrBox;
+
+/// A registry of prototype instances, keyed by `K`.
+///
+/// Each prototype is registered once and then shared. `get` lends out the
+/// prototype itself as the Borrowed variant, so that callers who only read
+/// from it never allocate. With the "trait-clone" feature, `get_owned`
+/// instead hands out a private copy as the Owned variant.
+///
+/// ```rust
+/// # use polymorph::flyweight::Flyweight;
+/// trait Glyph {
+///     fn width(&self) -> u8;
+/// }
+///
+/// struct Narrow;
+/// impl Glyph for Narrow {
+///     fn width(&self) -> u8 { 1 }
+/// }
+///
+/// let mut glyphs: Flyweight<char, dyn Glyph> = Flyweight::new();
+/// glyphs.register('i', Box::new(Narrow));
+///
+/// let glyph = glyphs.get(&'i').expect("Glyph not registered");
+/// assert_eq!(1, glyph.width());
+/// ```
+#[derive(Debug)]
+pub struct Flyweight<K, T: ?Sized> {
+    prototypes: HashMap<K, Box<T>>
+}
+
+impl<K, T: ?Sized> Default for Flyweight<K, T> {
+    fn default() -> Self {
+        Self {
+            prototypes: HashMap::new()
+        }
+    }
+}
+
+impl<K, T: ?Sized> Flyweight<K, T> where K: Eq + Hash {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a prototype under the given key.
+    ///
+    /// If a prototype was already registered for the key, it is replaced and returned.
+    pub fn register(&mut self, key: K, prototype: Box<T>) -> Option<Box<T>> {
+        self.prototypes.insert(key, prototype)
+    }
+
+    /// Removes and returns the prototype registered under the given key, if any
+    pub fn unregister<Q>(&mut self, key: &Q) -> Option<Box<T>>
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        self.prototypes.remove(key)
+    }
+
+    /// Obtains the shared prototype registered under the given key.
+    ///
+    /// The prototype is always returned as the Borrowed variant.
+    pub fn get<Q>(&self, key: &Q) -> Option<RefOrBox<'_, T>>
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        self.prototypes.get(key).map(|prototype| RefOrBox::Borrowed(prototype.as_ref()))
+    }
+
+    /// Whether a prototype is registered under the given key
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        self.prototypes.contains_key(key)
+    }
+
+    /// The number of registered prototypes
+    pub fn len(&self) -> usize {
+        self.prototypes.len()
+    }
+
+    /// Whether no prototypes are registered
+    pub fn is_empty(&self) -> bool {
+        self.prototypes.is_empty()
+    }
+}
+
+#[cfg(feature = "trait-clone")]
+impl<K, T: ?Sized> Flyweight<K, T> where K: Eq + Hash, T: dyn_clone::DynClone {
+    /// Obtains a private copy of the prototype registered under the given key.
+    /// This requires the "trait-clone" feature and relies on the dyn-clone crate.
+    ///
+    /// The prototype is cloned, and the clone is returned as the Owned variant.
+    ///
+    /// ```rust
+    /// # use polymorph::flyweight::Flyweight;
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// use dyn_clone::DynClone;
+    ///
+    /// trait Counter: DynClone {
+    ///     fn count(&self) -> u32;
+    ///     fn increment(&mut self);
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct SimpleCounter(u32);
+    /// impl Counter for SimpleCounter {
+    ///     fn count(&self) -> u32 { self.0 }
+    ///     fn increment(&mut self) { self.0 += 1; }
+    /// }
+    ///
+    /// let mut counters: Flyweight<&str, dyn Counter> = Flyweight::new();
+    /// counters.register("zero", Box::new(SimpleCounter(0)));
+    ///
+    /// if let Some(RefOrBox::Owned(mut counter)) = counters.get_owned("zero") {
+    ///     counter.increment();
+    ///     assert_eq!(1, counter.count());
+    /// }
+    /// assert_eq!(0, counters.get("zero").unwrap().count());
+    /// ```
+    pub fn get_owned<Q>(&self, key: &Q) -> Option<RefOrBox<'_, T>>
+        where K: Borrow<Q>, Q: ?Sized + Eq + Hash {
+
+        self.prototypes.get(key).map(|prototype| RefOrBox::Owned(dyn_clone::clone_box(prototype.as_ref())))
+    }
+}
+
+#[cfg(test)]
+#[path = "flyweight_tests.rs"]
+mod flyweight_tests;
@@ -0,0 +1,82 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::flyweight::*;
+use crate::ref_or_owned::RefOrBox;
+
+#[cfg(feature = "trait-clone")]
+trait Shape: dyn_clone::DynClone {
+    fn sides(&self) -> u8;
+}
+
+#[cfg(not(feature = "trait-clone"))]
+trait Shape {
+    fn sides(&self) -> u8;
+}
+
+#[derive(Clone)]
+struct Polygon {
+    sides: u8
+}
+
+impl Shape for Polygon {
+    fn sides(&self) -> u8 {
+        self.sides
+    }
+}
+
+fn shapes() -> Flyweight<&'static str, dyn Shape> {
+    let mut shapes: Flyweight<&'static str, dyn Shape> = Flyweight::new();
+    shapes.register("triangle", Box::new(Polygon { sides: 3 }));
+    shapes.register("square", Box::new(Polygon { sides: 4 }));
+    shapes
+}
+
+#[test]
+fn get_borrows_prototype() {
+    let shapes = shapes();
+    assert_eq!(2, shapes.len());
+    match shapes.get("triangle") {
+        Some(RefOrBox::Borrowed(triangle)) => assert_eq!(3, triangle.sides()),
+        _ => panic!("Wrong RefOrBox variant")
+    }
+    assert!(shapes.get("circle").is_none());
+}
+
+#[test]
+fn register_replaces_prototype() {
+    let mut shapes = shapes();
+    let previous = shapes.register("square", Box::new(Polygon { sides: 5 }));
+    assert_eq!(Some(4), previous.map(|square| square.sides()));
+    assert_eq!(5, shapes.get("square").unwrap().sides());
+
+    assert!(shapes.unregister("square").is_some());
+    assert!(!shapes.contains_key("square"));
+}
+
+#[test]
+#[cfg(feature = "trait-clone")]
+fn get_owned_clones_prototype() {
+    let shapes = shapes();
+    let square = match shapes.get_owned("square") {
+        Some(RefOrBox::Owned(square)) => square,
+        _ => panic!("Wrong RefOrBox variant")
+    };
+    let prototype = shapes.get("square").unwrap();
+    assert_eq!(4, square.sides());
+    assert!(!std::ptr::eq(&*square as *const dyn Shape as *const u8, &*prototype as *const dyn Shape as *const u8));
+    assert!(shapes.get_owned("circle").is_none());
+}
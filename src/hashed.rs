@@ -0,0 +1,114 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use crate::ref_or_owned::RefOrOwned;
+
+/// A `RefOrOwned` together with its precomputed hash.
+///
+/// The hash of the wrapped value is computed once, upon construction. Afterwards,
+/// hashing a `HashedMaybeOwned` only feeds the cached hash to the hasher. Equality
+/// first compares the cached hashes, and compares the values themselves only when
+/// the hashes match.
+///
+/// This reduces the cost of repeatedly probing maps with the same keys, especially
+/// when the keys are expensive to hash.
+///
+/// ```rust
+/// # use polymorph::hashed::HashedMaybeOwned;
+/// use std::collections::HashMap;
+///
+/// let long_key = "a".repeat(10_000);
+/// let key = HashedMaybeOwned::new(&long_key);
+///
+/// let mut first = HashMap::new();
+/// let mut second = HashMap::new();
+/// first.insert(HashedMaybeOwned::new(long_key.clone()), 1);
+/// second.insert(HashedMaybeOwned::new(&long_key), 2);
+///
+/// assert_eq!(Some(&1), first.get(&key));
+/// assert_eq!(Some(&2), second.get(&key));
+/// ```
+#[derive(Debug)]
+pub struct HashedMaybeOwned<'t, T: Hash> {
+    hash: u64,
+    value: RefOrOwned<'t, T>
+}
+
+impl<'t, T: Hash> HashedMaybeOwned<'t, T> {
+    /// Wraps the value, computing its hash
+    pub fn new<V>(value: V) -> Self where V: Into<RefOrOwned<'t, T>> {
+        let value = value.into();
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        Self {
+            hash: hasher.finish(),
+            value
+        }
+    }
+
+    /// The precomputed hash
+    pub fn precomputed_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Accesses the wrapped value
+    pub fn value(&self) -> &RefOrOwned<'t, T> {
+        &self.value
+    }
+
+    /// Discards the precomputed hash, yielding the wrapped value
+    pub fn into_inner(self) -> RefOrOwned<'t, T> {
+        self.value
+    }
+}
+
+impl<T: Hash> Deref for HashedMaybeOwned<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value.deref()
+    }
+}
+
+impl<T: Hash> AsRef<T> for HashedMaybeOwned<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T: Hash + PartialEq> PartialEq for HashedMaybeOwned<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.value == other.value
+    }
+}
+
+impl<T: Hash + Eq> Eq for HashedMaybeOwned<'_, T> {}
+
+impl<T: Hash> Hash for HashedMaybeOwned<'_, T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash)
+    }
+}
+
+#[cfg(test)]
+#[path = "hashed_tests.rs"]
+mod hashed_tests;
@@ -0,0 +1,69 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::hashed::*;
+use crate::ref_or_owned::RefOrOwned;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+thread_local! {
+    static HASH_CALLS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn hash_calls() -> u32 {
+    HASH_CALLS.with(|calls| calls.get())
+}
+
+#[derive(PartialEq, Eq)]
+struct CountingKey(u32);
+
+impl Hash for CountingKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        HASH_CALLS.with(|calls| calls.set(calls.get() + 1));
+        self.0.hash(state);
+    }
+}
+
+#[test]
+fn value_is_hashed_once() {
+    let key = CountingKey(1);
+    let hashed = HashedMaybeOwned::new(&key);
+    assert_eq!(1, hash_calls());
+
+    let mut first = HashSet::new();
+    let mut second = HashSet::new();
+    first.insert(HashedMaybeOwned::new(CountingKey(1)));
+    second.insert(HashedMaybeOwned::new(CountingKey(2)));
+    assert_eq!(3, hash_calls());
+
+    assert!(first.contains(&hashed));
+    assert!(!second.contains(&hashed));
+    assert_eq!(3, hash_calls());
+}
+
+#[test]
+fn borrowed_and_owned_are_equal() {
+    let value = String::from("value");
+    let borrowed: HashedMaybeOwned<String> = HashedMaybeOwned::new(&value);
+    let owned = HashedMaybeOwned::new(value.clone());
+    assert_eq!(borrowed.precomputed_hash(), owned.precomputed_hash());
+    assert_eq!(borrowed, owned);
+    assert_ne!(borrowed, HashedMaybeOwned::new(String::from("other")));
+
+    assert_eq!(5, owned.len());
+    assert!(matches!(borrowed.into_inner(), RefOrOwned::Borrowed(_)));
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use crate::ref_or_owned::RefOrBox;
+
+/// A `RefOrBox` compared and hashed by the address of its value, rather than the value itself.
+///
+/// Two `ByIdentity` are equal when they point to the same object: the same referent if
+/// borrowed, or the same box if owned. This suits caches keyed by the object itself,
+/// in which distinct objects must be kept apart even if they compare as equal. The value
+/// need not implement `PartialEq` or `Hash` at all.
+///
+/// Only `RefOrBox` is accepted, because its value does not move when the wrapper moves.
+///
+/// Values of zero-sized types, such as stateless trait implementors, all share one
+/// address. They follow `RefOrBox::ptr_eq` instead: borrowed values are identical if
+/// borrowed through the same reference, vtable included, while an owned value is only
+/// identical to its own wrapper.
+///
+/// ```rust
+/// # use polymorph::identity::ByIdentity;
+/// use std::collections::HashSet;
+///
+/// let first = String::from("value");
+/// let second = first.clone();
+///
+/// let mut seen = HashSet::new();
+/// assert!(seen.insert(ByIdentity::from(&first)));
+/// assert!(seen.insert(ByIdentity::from(&second)));
+/// assert!(!seen.insert(ByIdentity::from(&first)));
+/// ```
+#[derive(Debug)]
+pub struct ByIdentity<'t, T: ?Sized>(pub RefOrBox<'t, T>);
+
+impl<'t, T: ?Sized> ByIdentity<'t, T> {
+    /// Wraps the value
+    pub fn new<V>(value: V) -> Self where V: Into<RefOrBox<'t, T>> {
+        Self(value.into())
+    }
+
+    /// Discards the identity semantics, yielding the wrapped value
+    pub fn into_inner(self) -> RefOrBox<'t, T> {
+        self.0
+    }
+
+    fn address(&self) -> *const u8 {
+        (self.0.deref() as *const T).cast::<u8>()
+    }
+}
+
+impl<'t, T: ?Sized> From<&'t T> for ByIdentity<'t, T> {
+    fn from(value: &'t T) -> Self {
+        Self(RefOrBox::Borrowed(value))
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for ByIdentity<'_, T> {
+    fn from(value: Box<T>) -> Self {
+        Self(RefOrBox::Owned(value))
+    }
+}
+
+impl<'t, T: ?Sized> From<RefOrBox<'t, T>> for ByIdentity<'t, T> {
+    fn from(value: RefOrBox<'t, T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: ?Sized> Deref for ByIdentity<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.deref()
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for ByIdentity<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T: ?Sized> PartialEq for ByIdentity<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+}
+
+impl<T: ?Sized> Eq for ByIdentity<'_, T> {}
+
+impl<T: ?Sized> Hash for ByIdentity<'_, T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address().hash(state)
+    }
+}
+
+#[cfg(test)]
+#[path = "identity_tests.rs"]
+mod identity_tests;
@@ -0,0 +1,95 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::identity::*;
+use crate::ref_or_owned::RefOrBox;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+#[test]
+fn equal_values_are_distinct() {
+    let first = String::from("value");
+    let second = first.clone();
+    assert_eq!(ByIdentity::from(&first), ByIdentity::from(&first));
+    assert_ne!(ByIdentity::from(&first), ByIdentity::from(&second));
+}
+
+#[test]
+fn owned_box_keeps_identity() {
+    let owned: ByIdentity<dyn Display> = ByIdentity::from(Box::new(3) as Box<dyn Display>);
+    let view = ByIdentity(owned.0.as_borrowed());
+    assert!(owned == view);
+    assert!(owned != ByIdentity::from(Box::new(3) as Box<dyn Display>));
+}
+
+#[test]
+fn cache_keyed_by_object() {
+    let objects = [String::from("same"), String::from("same")];
+    let mut cache = HashMap::new();
+    for object in &objects {
+        *cache.entry(ByIdentity::new(object)).or_insert(0) += 1;
+    }
+    *cache.entry(ByIdentity::new(&objects[0])).or_insert(0) += 1;
+
+    assert_eq!(2, cache.len());
+    assert_eq!(Some(&2), cache.get(&ByIdentity::from(&objects[0])));
+    assert_eq!(Some(&1), cache.get(&ByIdentity::from(&objects[1])));
+    assert!(matches!(ByIdentity::from(&objects[0]).into_inner(), RefOrBox::Borrowed(_)));
+}
+
+trait Greeter {
+    fn greet(&self) -> &'static str;
+}
+
+struct Formal;
+struct Casual;
+
+impl Greeter for Formal {
+    fn greet(&self) -> &'static str {
+        "Good day"
+    }
+}
+
+impl Greeter for Casual {
+    fn greet(&self) -> &'static str {
+        "Hi"
+    }
+}
+
+#[test]
+fn zero_sized_boxes_are_distinct() {
+    let formal: ByIdentity<dyn Greeter> = ByIdentity::from(Box::new(Formal) as Box<dyn Greeter>);
+    let casual: ByIdentity<dyn Greeter> = ByIdentity::from(Box::new(Casual) as Box<dyn Greeter>);
+    let second_formal: ByIdentity<dyn Greeter> = ByIdentity::from(Box::new(Formal) as Box<dyn Greeter>);
+    assert!(formal == formal);
+    assert!(formal != casual);
+    assert!(formal != second_formal);
+
+    let mut greeters = HashSet::new();
+    assert!(greeters.insert(formal));
+    assert!(greeters.insert(casual));
+    assert!(greeters.insert(second_formal));
+    assert_eq!(3, greeters.len());
+}
+
+#[test]
+fn zero_sized_references() {
+    let formal: &dyn Greeter = &Formal;
+    let casual: &dyn Greeter = &Casual;
+    assert_eq!("Good day", ByIdentity::from(formal).greet());
+    assert!(ByIdentity::from(formal) == ByIdentity::from(formal));
+    assert!(ByIdentity::from(formal) != ByIdentity::from(casual));
+}
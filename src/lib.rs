@@ -17,11 +17,13 @@
 #![forbid(unsafe_code)]
 
 //!
-//! Currently only the ref_or_owned module is provided, but if or when
-//! more utilities are introduced, they will be placed into separate
-//! modules.
+//! The core of the crate is the ref_or_owned module. Further utilities
+//! built on top of it are placed into separate modules.
 //!
 
+#[cfg(feature = "derive")]
+pub use polymorph_derive::Reborrow;
+
 ///
 /// Contains abstractions over references and ownership. Provides types
 /// which may represent either a borrowed reference or an owned value.
@@ -30,3 +32,85 @@
 /// or immutable. The right enum should be chosen on these bases.
 ///
 pub mod ref_or_owned;
+
+///
+/// Provides a registry of shared prototype instances. Prototypes are lent out
+/// as borrowed values, or cloned into owned values on request.
+///
+pub mod flyweight;
+
+///
+/// Provides chains of transformations whose stages may be borrowed
+/// functions or owned boxed closures.
+///
+pub mod pipeline;
+
+///
+/// Provides decoration of borrowed or owned objects with hooks which run
+/// around each call.
+///
+pub mod decorator;
+
+///
+/// Provides a state machine driver whose states may be shared borrowed
+/// states or owned boxed states.
+///
+pub mod state;
+
+///
+/// Provides ordering helpers for slices of wrappers, notably collections
+/// of borrowed or boxed trait objects.
+///
+pub mod poly_slice;
+
+///
+/// Provides a wrapper which caches the hash of a borrowed or owned value,
+/// for keys which are probed across many maps.
+///
+pub mod hashed;
+
+///
+/// Provides a value which is either ready, or computed by a future
+/// upon first access.
+///
+pub mod async_lazy;
+
+///
+/// Provides a copy-on-write overlay over a borrowed map.
+///
+pub mod cow_map;
+
+///
+/// Provides object-safe comparison and hashing traits, so that trait objects
+/// and wrappers holding them can be compared and hashed.
+///
+pub mod dyn_cmp;
+
+///
+/// Provides an error type which is either a borrowed or a boxed error.
+///
+pub mod error;
+
+///
+/// Provides a wrapper which compares and hashes borrowed or boxed values by
+/// their address, for caches keyed by object identity.
+///
+pub mod identity;
+
+///
+/// Provides functions for `#[serde(with = "...")]`, which always deserialize
+/// the Owned variant, and seeds which thread user state into deserialization
+/// of the wrappers. This requires the "serde" feature.
+///
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+
+///
+/// Provides a registry of constructors for deserializing tagged trait objects.
+/// This requires the "erased-serde" feature.
+///
+#[cfg(feature = "erased-serde")]
+pub mod registry;
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,104 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ref_or_owned::RefOrBox;
+
+/// An ordered chain of transformations of `T`.
+///
+/// Each stage is either a borrowed function, such as one which is statically known
+/// and lives elsewhere, or an owned boxed function, such as a closure configured
+/// at runtime. Running the pipeline passes the value through every stage in order.
+///
+/// ```rust
+/// # use polymorph::pipeline::Pipeline;
+/// fn trim(input: String) -> String {
+///     input.trim().to_string()
+/// }
+///
+/// let suffix = String::from("!");
+/// let mut pipeline = Pipeline::new();
+/// pipeline.push_ref(&trim);
+/// pipeline.push_owned(move |input: String| input + &suffix);
+///
+/// assert_eq!("hello!", pipeline.run(String::from("  hello ")));
+/// ```
+pub struct Pipeline<'t, T: 't> {
+    stages: Vec<RefOrBox<'t, dyn Fn(T) -> T + 't>>
+}
+
+impl<T> Default for Pipeline<'_, T> {
+    fn default() -> Self {
+        Self {
+            stages: Vec::new()
+        }
+    }
+}
+
+impl<'t, T> Pipeline<'t, T> {
+    /// Creates a pipeline without any stages. Running it returns the input unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a borrowed stage
+    pub fn push_ref<F>(&mut self, stage: &'t F) where F: Fn(T) -> T + 't {
+        self.stages.push(RefOrBox::Borrowed(stage));
+    }
+
+    /// Appends an owned stage, which will be boxed
+    pub fn push_owned<F>(&mut self, stage: F) where F: Fn(T) -> T + 't {
+        self.stages.push(RefOrBox::Owned(Box::new(stage)));
+    }
+
+    /// Appends a stage which may be borrowed or owned
+    pub fn push(&mut self, stage: RefOrBox<'t, dyn Fn(T) -> T + 't>) {
+        self.stages.push(stage);
+    }
+
+    /// The number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Whether there are no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Passes the input through each stage, in order
+    pub fn run(&self, input: T) -> T {
+        self.stages.iter().fold(input, |value, stage| stage(value))
+    }
+
+    /// Combines all stages into a single function
+    ///
+    /// ```rust
+    /// # use polymorph::pipeline::Pipeline;
+    /// let mut pipeline = Pipeline::new();
+    /// pipeline.push_owned(|value: u32| value + 1);
+    /// pipeline.push_owned(|value: u32| value * 10);
+    ///
+    /// let composed = pipeline.compose();
+    /// assert_eq!(vec![10, 20], vec![0, 1].into_iter().map(composed).collect::<Vec<_>>());
+    /// ```
+    pub fn compose(self) -> impl Fn(T) -> T + 't {
+        move |input| self.run(input)
+    }
+}
+
+#[cfg(test)]
+#[path = "pipeline_tests.rs"]
+mod pipeline_tests;
@@ -0,0 +1,54 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::pipeline::*;
+use crate::ref_or_owned::RefOrBox;
+
+fn double(value: i32) -> i32 {
+    value * 2
+}
+
+#[test]
+fn empty_pipeline_is_identity() {
+    let pipeline: Pipeline<i32> = Pipeline::new();
+    assert!(pipeline.is_empty());
+    assert_eq!(7, pipeline.run(7));
+}
+
+#[test]
+fn stages_run_in_order() {
+    let offset = 3;
+    let add_offset = move |value: i32| value + offset;
+
+    let mut pipeline = Pipeline::new();
+    pipeline.push_ref(&double);
+    pipeline.push_owned(move |value: i32| value - offset);
+    pipeline.push(RefOrBox::Borrowed(&add_offset));
+    assert_eq!(3, pipeline.len());
+
+    assert_eq!(10, pipeline.run(5));
+    assert_eq!(-2, pipeline.run(-1));
+}
+
+#[test]
+fn compose_runs_all_stages() {
+    let mut pipeline = Pipeline::new();
+    pipeline.push_ref(&double);
+    pipeline.push_ref(&double);
+
+    let quadruple = pipeline.compose();
+    assert_eq!(12, quadruple(3));
+}
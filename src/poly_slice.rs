@@ -0,0 +1,77 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Deref;
+
+/// Ordering helpers for slices of wrappers, such as `[RefOrBox<'_, dyn Trait>]`.
+///
+/// Elements are ordered by a key which the caller extracts from the wrapped
+/// value, so that heterogeneous collections of trait objects may be sorted and
+/// searched without unwrapping each element.
+///
+/// This trait is implemented for every slice whose elements dereference to `T`,
+/// and is thus available on vectors as well.
+///
+/// ```rust
+/// # use polymorph::poly_slice::PolySlice;
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// trait Animal {
+///     fn legs(&self) -> u8;
+/// }
+///
+/// struct Bird;
+/// impl Animal for Bird {
+///     fn legs(&self) -> u8 { 2 }
+/// }
+///
+/// struct Dog;
+/// impl Animal for Dog {
+///     fn legs(&self) -> u8 { 4 }
+/// }
+///
+/// let bird = Bird;
+/// let mut animals: Vec<RefOrBox<dyn Animal>> = vec![
+///     RefOrBox::Owned(Box::new(Dog)),
+///     RefOrBox::Borrowed(&bird)
+/// ];
+/// animals.sort_poly(|animal| animal.legs());
+/// assert_eq!(Ok(1), animals.binary_search_poly(&4, |animal| animal.legs()));
+/// ```
+pub trait PolySlice<T: ?Sized> {
+    /// Sorts the slice by the key extracted from each wrapped value.
+    ///
+    /// The sort is stable, and the key extractor is called once or more per element.
+    fn sort_poly<K, F>(&mut self, key: F) where K: Ord, F: FnMut(&T) -> K;
+
+    /// Binary searches the slice, which must be sorted by the same key, for the given key.
+    ///
+    /// Returns the same result as `slice::binary_search_by_key`.
+    fn binary_search_poly<K, F>(&self, key: &K, extractor: F) -> Result<usize, usize> where K: Ord, F: FnMut(&T) -> K;
+}
+
+impl<T: ?Sized, W> PolySlice<T> for [W] where W: Deref<Target = T> {
+    fn sort_poly<K, F>(&mut self, mut key: F) where K: Ord, F: FnMut(&T) -> K {
+        self.sort_by_key(|wrapper| key(wrapper.deref()))
+    }
+
+    fn binary_search_poly<K, F>(&self, key: &K, mut extractor: F) -> Result<usize, usize> where K: Ord, F: FnMut(&T) -> K {
+        self.binary_search_by(|wrapper| extractor(wrapper.deref()).cmp(key))
+    }
+}
+
+#[cfg(test)]
+#[path = "poly_slice_tests.rs"]
+mod poly_slice_tests;
@@ -0,0 +1,79 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::poly_slice::*;
+use crate::ref_or_owned::{RefOrBox, RefOrOwned};
+
+trait Weighted {
+    fn weight(&self) -> u32;
+}
+
+struct Crate(u32);
+struct Feather;
+
+impl Weighted for Crate {
+    fn weight(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Weighted for Feather {
+    fn weight(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn sort_and_search_ref_or_box() {
+    let light_crate = Crate(3);
+    let mut items: Vec<RefOrBox<dyn Weighted>> = vec![
+        RefOrBox::Owned(Box::new(Crate(10))),
+        RefOrBox::Borrowed(&light_crate),
+        RefOrBox::Owned(Box::new(Feather))
+    ];
+    items.sort_poly(|item| item.weight());
+
+    let weights: Vec<u32> = items.iter().map(|item| item.weight()).collect();
+    assert_eq!(vec![0, 3, 10], weights);
+    assert_eq!(Ok(1), items.binary_search_poly(&3, |item| item.weight()));
+    assert_eq!(Err(2), items.binary_search_poly(&5, |item| item.weight()));
+}
+
+#[test]
+fn sort_is_stable() {
+    let first = (1, 'b');
+    let second = (2, 'c');
+    let third = (1, 'e');
+    let mut items = [
+        RefOrOwned::Owned((2, 'a')),
+        RefOrOwned::Borrowed(&first),
+        RefOrOwned::Borrowed(&second),
+        RefOrOwned::Owned((1, 'd')),
+        RefOrOwned::Borrowed(&third),
+        RefOrOwned::Owned((2, 'f'))
+    ];
+    items.sort_poly(|item| item.0);
+    let payloads: String = items.iter().map(|item| item.1).collect();
+    assert_eq!("bdeacf", payloads);
+}
+
+#[test]
+fn sort_sized_wrappers() {
+    let borrowed = String::from("b");
+    let mut items = [RefOrOwned::Owned(String::from("ccc")), RefOrOwned::Borrowed(&borrowed)];
+    items.sort_poly(|item| item.len());
+    assert_eq!("b", items[0].as_str());
+}
@@ -58,7 +58,6 @@ use std::hash::{Hash, Hasher};
 ///   my_struct.my_func()
 /// }
 /// ```
-#[derive(Debug)]
 pub enum RefOrOwned<'t, T: 't> {
     Borrowed(&'t T),
     Owned(T)
@@ -72,6 +71,172 @@ impl<'t, T> From<&'t T> for RefOrOwned<'t, T> {
 
 ref_or_owned_impls!(RefOrOwned);
 
+/// Borrowed values clone the reference, while owned values clone the value.
+impl<T: Clone> Clone for RefOrOwned<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(borrowed_value) => Self::Borrowed(borrowed_value),
+            Self::Owned(owned_value) => Self::Owned(owned_value.clone())
+        }
+    }
+}
+
+/// Small values such as integers can be passed around by value, whichever variant holds them.
+impl<T: Copy> Copy for RefOrOwned<'_, T> {}
+
+impl<'t, T: 't> RefOrOwned<'t, T> {
+    /// Converts to a `RefOrBox` of an unsized type, usually a trait object.
+    ///
+    /// A borrowed value stays borrowed, and only an owned value is moved into a box.
+    /// The two functions perform the unsizing coercion for each variant. Usually, closures
+    /// which cast with `as _` suffice, since the target type is inferred.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+    /// use std::fmt::Display;
+    ///
+    /// let number = 5;
+    /// let borrowed: RefOrBox<dyn Display> = RefOrOwned::Borrowed(&number).unsize(|r| r as _, |b| b as _);
+    /// assert!(matches!(borrowed, RefOrBox::Borrowed(_)));
+    ///
+    /// let owned: RefOrBox<dyn Display> = RefOrOwned::Owned(6).unsize(|r| r as _, |b| b as _);
+    /// assert_eq!("6", owned.to_string());
+    /// ```
+    pub fn unsize<U, R, B>(self, coerce_ref: R, coerce_box: B) -> RefOrBox<'t, U>
+        where U: ?Sized + 't, R: FnOnce(&'t T) -> &'t U, B: FnOnce(Box<T>) -> Box<U> {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrBox::Borrowed(coerce_ref(borrowed_value)),
+            Self::Owned(owned_value) => RefOrBox::Owned(coerce_box(Box::new(owned_value)))
+        }
+    }
+
+    /// Acquires a mutable reference to the value if it is owned. This allows owned data
+    /// to be mutated in place, falling back to other means only when it is borrowed.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let mut owned: RefOrOwned<Vec<u8>> = RefOrOwned::Owned(vec![1]);
+    /// if let Some(list) = owned.try_as_mut() {
+    ///     list.push(2);
+    /// }
+    /// assert_eq!(vec![1, 2], *owned);
+    ///
+    /// let shared = vec![1];
+    /// assert!(RefOrOwned::Borrowed(&shared).try_as_mut().is_none());
+    /// ```
+    pub fn try_as_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Self::Borrowed(_) => None,
+            Self::Owned(owned_value) => Some(owned_value)
+        }
+    }
+}
+
+impl<T: Clone> RefOrOwned<'_, T> {
+    /// Acquires a mutable reference to the owned value, like `Cow::to_mut`.
+    ///
+    /// If the data is borrowed, it is cloned once and the wrapper becomes Owned.
+    /// Later calls return the same owned value without cloning.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let original = vec![1, 2];
+    /// let mut wrapper = RefOrOwned::Borrowed(&original);
+    /// wrapper.to_mut().push(3);
+    ///
+    /// assert_eq!(vec![1, 2], original);
+    /// assert_eq!(vec![1, 2, 3], *wrapper);
+    /// assert!(wrapper.is_owned());
+    /// ```
+    pub fn to_mut(&mut self) -> &mut T {
+        if let Self::Borrowed(borrowed_value) = *self {
+            *self = Self::Owned(borrowed_value.clone());
+        }
+        match self {
+            Self::Owned(owned_value) => owned_value,
+            Self::Borrowed(_) => unreachable!("Borrowed value was replaced")
+        }
+    }
+
+    /// Obtains a boxed value of T, for APIs which require boxes. A borrowed value is
+    /// cloned into a new box, and an owned value is moved into one.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let shared = String::from("shared");
+    /// let boxed: Box<String> = RefOrOwned::Borrowed(&shared).into_box();
+    /// assert_eq!(shared, *boxed);
+    /// ```
+    pub fn into_box(self) -> Box<T> {
+        match self {
+            Self::Borrowed(borrowed_value) => Box::new(borrowed_value.clone()),
+            Self::Owned(owned_value) => Box::new(owned_value)
+        }
+    }
+}
+
+impl<I: Iterator + Clone> RefOrOwned<'_, I> {
+    /// Yields an iterator which can be consumed by value. A borrowed iterator is cloned,
+    /// so that the lender's iterator is not advanced, whereas an owned iterator is moved.
+    ///
+    /// This is an alias of `into_owned`, for readability at call sites which iterate.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// fn total(numbers: RefOrOwned<'_, std::ops::Range<u32>>) -> u32 {
+    ///     numbers.iter_cloned().sum()
+    /// }
+    ///
+    /// let kept = 1..4;
+    /// assert_eq!(6, total(RefOrOwned::Borrowed(&kept)));
+    /// assert_eq!(6, total(RefOrOwned::Owned(1..4)));
+    /// assert_eq!(1..4, kept);
+    /// ```
+    pub fn iter_cloned(self) -> I {
+        self.into_owned()
+    }
+}
+
+/// Helpers for lazily initializing an optional `RefOrOwned`.
+///
+/// This trait is implemented for `Option<RefOrOwned<'t, T>>`, a common type for
+/// fields which are filled in on first use.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{OptionRefOrOwnedExt, RefOrOwned};
+/// struct Config<'t> {
+///     name: Option<RefOrOwned<'t, String>>
+/// }
+///
+/// impl<'t> Config<'t> {
+///     fn name(&mut self) -> &str {
+///         self.name.get_or_insert_owned_with(|| String::from("default"))
+///     }
+/// }
+///
+/// let mut config = Config { name: None };
+/// assert_eq!("default", config.name());
+/// ```
+pub trait OptionRefOrOwnedExt<'t, T> {
+    /// Inserts the borrowed value if `None`, then returns a mutable reference to the contained wrapper
+    fn get_or_insert_borrowed(&mut self, value: &'t T) -> &mut RefOrOwned<'t, T>;
+
+    /// Inserts an owned value computed from `f` if `None`, then returns a mutable reference
+    /// to the contained wrapper
+    fn get_or_insert_owned_with<F>(&mut self, f: F) -> &mut RefOrOwned<'t, T> where F: FnOnce() -> T;
+}
+
+impl<'t, T> OptionRefOrOwnedExt<'t, T> for Option<RefOrOwned<'t, T>> {
+    fn get_or_insert_borrowed(&mut self, value: &'t T) -> &mut RefOrOwned<'t, T> {
+        self.get_or_insert(RefOrOwned::Borrowed(value))
+    }
+
+    fn get_or_insert_owned_with<F>(&mut self, f: F) -> &mut RefOrOwned<'t, T> where F: FnOnce() -> T {
+        self.get_or_insert_with(|| RefOrOwned::Owned(f()))
+    }
+}
+
 /// A type which can be either a mutable reference, or an owned value.
 /// RefMutOrOwned requires sized types. For unsized types, use `RefMutOrBox` instead.
 ///
@@ -109,7 +274,9 @@ ref_or_owned_impls!(RefOrOwned);
 ///   my_struct.my_func()
 /// }
 /// ```
-#[derive(Debug)]
+///
+/// As with `&mut T`, this type is never `UnwindSafe`. Wrap it in `AssertUnwindSafe`
+/// to move it into `catch_unwind`.
 pub enum RefMutOrOwned<'t, T: 't> {
     Borrowed(&'t mut T),
     Owned(T)
@@ -125,7 +292,7 @@ impl<T> DerefMut for RefMutOrOwned<'_, T> {
 
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
-            Self::Borrowed(borrowed_value) => *borrowed_value,
+            Self::Borrowed(borrowed_value) => borrowed_value,
             Self::Owned(owned_value) => owned_value
         }
     }
@@ -147,6 +314,48 @@ impl<T> BorrowMut<T> for RefMutOrOwned<'_, T> {
 
 ref_or_owned_impls!(RefMutOrOwned);
 
+impl<'t, T: 't> RefMutOrOwned<'t, T> {
+    /// Converts to a `RefMutOrBox` of an unsized type, usually a trait object.
+    ///
+    /// A borrowed value stays borrowed, and only an owned value is moved into a box.
+    /// The two functions perform the unsizing coercion for each variant. Usually, closures
+    /// which cast with `as _` suffice, since the target type is inferred.
+    pub fn unsize<U, R, B>(self, coerce_ref: R, coerce_box: B) -> RefMutOrBox<'t, U>
+        where U: ?Sized + 't, R: FnOnce(&'t mut T) -> &'t mut U, B: FnOnce(Box<T>) -> Box<U> {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefMutOrBox::Borrowed(coerce_ref(borrowed_value)),
+            Self::Owned(owned_value) => RefMutOrBox::Owned(coerce_box(Box::new(owned_value)))
+        }
+    }
+
+    /// Lends out a shorter-lived Borrowed wrapper around the value, as with reborrowing
+    /// `&mut T`. The original wrapper can be used again once the reborrow ends.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefMutOrOwned;
+    /// fn append(mut list: RefMutOrOwned<'_, Vec<u8>>, value: u8) {
+    ///     list.push(value);
+    /// }
+    ///
+    /// let mut list: RefMutOrOwned<Vec<u8>> = RefMutOrOwned::Owned(Vec::new());
+    /// append(list.reborrow(), 1);
+    /// append(list.reborrow(), 2);
+    /// assert_eq!(vec![1, 2], *list);
+    /// ```
+    pub fn reborrow(&mut self) -> RefMutOrOwned<'_, T> {
+        RefMutOrOwned::Borrowed(self.deref_mut())
+    }
+}
+
+impl<T: DerefMut> RefMutOrOwned<'_, T> {
+    /// Views the value mutably through its `DerefMut` target, as with `Option::as_deref_mut`.
+    /// The result borrows from this wrapper, so nothing is cloned.
+    pub fn as_deref_mut(&mut self) -> RefMutOrBox<'_, T::Target> {
+        RefMutOrBox::Borrowed(self.deref_mut().deref_mut())
+    }
+}
+
 /// A type which can be either an immutable reference, or an owned boxed value.
 /// Box is used for the owned variant because this type is primarily intended for
 /// use with unsized types, most particularly trait objects. For sized types,
@@ -183,7 +392,46 @@ ref_or_owned_impls!(RefMutOrOwned);
 ///   my_trait.my_func()
 /// }
 /// ```
-#[derive(Debug)]
+///
+/// Trait objects need not be `'static`, even in the Owned variant. Written without an
+/// explicit bound, `RefOrBox<'t, dyn MyTrait>` stands for `RefOrBox<'t, dyn MyTrait + 't>`,
+/// so owned boxes may hold closures or other values which borrow for `'t`. A longer
+/// lifetime may be spelled out, such as `RefOrBox<'t, dyn MyTrait + 'a>` where `'a: 't`,
+/// when the trait object must outlive the wrapper.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// fn prefix_checker<'t>(prefix: &'t str, exact: bool) -> RefOrBox<'t, dyn Fn(&str) -> bool + 't> {
+///     static ACCEPT_ALL: fn(&str) -> bool = |_| true;
+///     if prefix.is_empty() {
+///         RefOrBox::Borrowed(&ACCEPT_ALL)
+///     } else if exact {
+///         RefOrBox::Owned(Box::new(move |input: &str| input == prefix))
+///     } else {
+///         RefOrBox::Owned(Box::new(move |input: &str| input.starts_with(prefix)))
+///     }
+/// }
+///
+/// let prefix = String::from("poly");
+/// assert!(prefix_checker(&prefix, false)("polymorph"));
+/// ```
+///
+/// `UnwindSafe` and `RefUnwindSafe` are derived from `T`. Because either variant may be
+/// present, `RefOrBox<dyn MyTrait>` is `UnwindSafe` only if the trait object is both
+/// `UnwindSafe` and `RefUnwindSafe`. Spell out both bounds to capture it in `catch_unwind`.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// use std::panic::{self, RefUnwindSafe, UnwindSafe};
+///
+/// trait Plugin {
+///   fn run(&self) -> u8;
+/// }
+///
+/// fn run_guarded(plugin: RefOrBox<'_, dyn Plugin + UnwindSafe + RefUnwindSafe>) -> Option<u8> {
+///   panic::catch_unwind(move || plugin.run()).ok()
+/// }
+/// ```
 pub enum RefOrBox<'t, T: ?Sized + 't> {
     Borrowed(&'t T),
     Owned(Box<T>)
@@ -197,6 +445,91 @@ impl<'t, T: ?Sized> From<&'t T> for RefOrBox<'t, T> {
 
 ref_or_box_impls!(RefOrBox);
 
+impl<'t, T: ?Sized> RefOrBox<'t, T> {
+    /// Obtains a plain reference, by returning a borrowed reference directly or leaking
+    /// an owned box. The leaked memory is never freed.
+    ///
+    /// This is intended for values which live for the rest of the program, such as
+    /// configuration interned at startup.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let config: RefOrBox<'static, str> = RefOrBox::Owned(Box::from("loaded at startup"));
+    /// let config: &'static str = config.leak();
+    /// assert_eq!("loaded at startup", config);
+    /// ```
+    pub fn leak(self) -> &'t T {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value,
+            Self::Owned(owned_box) => Box::leak(owned_box)
+        }
+    }
+
+    /// Acquires a mutable reference to the value if it is owned. This allows owned data
+    /// to be mutated in place, falling back to other means only when it is borrowed.
+    pub fn try_as_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Self::Borrowed(_) => None,
+            Self::Owned(owned_box) => Some(owned_box)
+        }
+    }
+}
+
+/// Borrowed values clone the reference, while owned values are cloned into a
+/// new box. This requires the "trait-clone" feature and relies on the dyn-clone crate.
+#[cfg(feature = "trait-clone")]
+impl<T: ?Sized> Clone for RefOrBox<'_, T> where T: dyn_clone::DynClone {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(borrowed_value) => Self::Borrowed(borrowed_value),
+            Self::Owned(owned_box) => Self::Owned(dyn_clone::clone_box(&**owned_box))
+        }
+    }
+}
+
+#[cfg(feature = "trait-clone")]
+impl<T: ?Sized> RefOrBox<'_, T> where T: dyn_clone::DynClone {
+    /// Acquires a mutable reference to the owned value, like `Cow::to_mut`. This
+    /// requires the "trait-clone" feature and relies on the dyn-clone crate.
+    ///
+    /// If the data is borrowed, it is cloned into a box once and the wrapper becomes
+    /// Owned. Later calls return the same owned value without cloning.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// use dyn_clone::DynClone;
+    ///
+    /// trait Counter: DynClone {
+    ///   fn increment(&mut self) -> u64;
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct SimpleCounter(u64);
+    ///
+    /// impl Counter for SimpleCounter {
+    ///   fn increment(&mut self) -> u64 {
+    ///     self.0 += 1;
+    ///     self.0
+    ///   }
+    /// }
+    ///
+    /// let shared = SimpleCounter(0);
+    /// let mut counter: RefOrBox<dyn Counter> = RefOrBox::Borrowed(&shared);
+    /// assert_eq!(1, counter.to_mut().increment());
+    /// assert_eq!(2, counter.to_mut().increment());
+    /// assert_eq!(0, shared.0);
+    /// ```
+    pub fn to_mut(&mut self) -> &mut T {
+        if let Self::Borrowed(borrowed_value) = *self {
+            *self = Self::Owned(dyn_clone::clone_box(borrowed_value));
+        }
+        match self {
+            Self::Owned(owned_value) => owned_value,
+            Self::Borrowed(_) => unreachable!("Borrowed value was replaced")
+        }
+    }
+}
+
 /// A type which can be either a mutable reference, or an owned boxed value.
 /// Box is used for the owned variant because this type is primarily intended for
 /// use with unsized types, most particularly trait objects. For sized types,
@@ -233,7 +566,9 @@ ref_or_box_impls!(RefOrBox);
 ///   my_trait.my_func()
 /// }
 /// ```
-#[derive(Debug)]
+///
+/// As with `&mut T`, this type is never `UnwindSafe`. Wrap it in `AssertUnwindSafe`
+/// to move it into `catch_unwind`.
 pub enum RefMutOrBox<'t, T: ?Sized + 't> {
     Borrowed(&'t mut T),
     Owned(Box<T>)
@@ -249,7 +584,7 @@ impl<T: ?Sized> DerefMut for RefMutOrBox<'_, T> {
 
     fn deref_mut(&mut self) -> &mut Self::Target {
         match self {
-            Self::Borrowed(borrowed_value) => *borrowed_value,
+            Self::Borrowed(borrowed_value) => borrowed_value,
             Self::Owned(owned_box) => owned_box.deref_mut()
         }
     }
@@ -271,6 +606,115 @@ impl<T: ?Sized> BorrowMut<T> for RefMutOrBox<'_, T> {
 
 ref_or_box_impls!(RefMutOrBox);
 
+impl<'t, T: ?Sized> RefMutOrBox<'t, T> {
+    /// Obtains a plain mutable reference, by returning a borrowed reference directly or
+    /// leaking an owned box. The leaked memory is never freed.
+    pub fn leak(self) -> &'t mut T {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value,
+            Self::Owned(owned_box) => Box::leak(owned_box)
+        }
+    }
+
+    /// Lends out a shorter-lived Borrowed wrapper around the value, as with reborrowing
+    /// `&mut T`. The original wrapper can be used again once the reborrow ends.
+    pub fn reborrow(&mut self) -> RefMutOrBox<'_, T> {
+        RefMutOrBox::Borrowed(self.deref_mut())
+    }
+}
+
+kind_impls!(RefOrOwned<T>);
+kind_impls!(RefMutOrOwned<T>);
+kind_impls!(RefOrBox<T: ?Sized>);
+kind_impls!(RefMutOrBox<T: ?Sized>);
+
+// Generic code bounded on Iterator would refuse the wrappers despite Deref
+iterator_impls!(RefMutOrOwned<I>);
+iterator_impls!(RefMutOrBox<I: ?Sized>);
+extend_impls!(RefMutOrOwned<T>);
+extend_impls!(RefMutOrBox<T: ?Sized>);
+
+reference_comparison_impls!(RefOrOwned<T>);
+reference_comparison_impls!(RefMutOrOwned<T>);
+reference_comparison_impls!(RefOrBox<T: ?Sized>);
+reference_comparison_impls!(RefMutOrBox<T: ?Sized>);
+
+// A blanket PartialEq<T> would overlap with the comparisons between wrappers,
+// so comparisons against bare values are provided for a fixed list of common types
+value_comparison_impls!(RefOrOwned; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String);
+value_comparison_impls!(RefMutOrOwned; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String);
+value_comparison_impls!(RefOrBox; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String);
+value_comparison_impls!(RefMutOrBox; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String);
+
+cross_comparison_impls!(RefOrOwned<T>, RefMutOrOwned<U>);
+cross_comparison_impls!(RefOrOwned<T>, RefOrBox<U: ?Sized>);
+cross_comparison_impls!(RefOrOwned<T>, RefMutOrBox<U: ?Sized>);
+cross_comparison_impls!(RefMutOrOwned<T>, RefOrOwned<U>);
+cross_comparison_impls!(RefMutOrOwned<T>, RefOrBox<U: ?Sized>);
+cross_comparison_impls!(RefMutOrOwned<T>, RefMutOrBox<U: ?Sized>);
+cross_comparison_impls!(RefOrBox<T: ?Sized>, RefOrOwned<U>);
+cross_comparison_impls!(RefOrBox<T: ?Sized>, RefMutOrOwned<U>);
+cross_comparison_impls!(RefOrBox<T: ?Sized>, RefMutOrBox<U: ?Sized>);
+cross_comparison_impls!(RefMutOrBox<T: ?Sized>, RefOrOwned<U>);
+cross_comparison_impls!(RefMutOrBox<T: ?Sized>, RefMutOrOwned<U>);
+cross_comparison_impls!(RefMutOrBox<T: ?Sized>, RefOrBox<U: ?Sized>);
+
+#[path = "ref_or_owned_variant.rs"]
+mod ref_or_owned_variant;
+
+pub use ref_or_owned_variant::*;
+
+#[path = "ref_or_owned_convert.rs"]
+mod ref_or_owned_convert;
+
+#[path = "ref_or_owned_cow.rs"]
+mod ref_or_owned_cow;
+
+#[path = "ref_or_owned_map.rs"]
+mod ref_or_owned_map;
+
+#[path = "ref_or_owned_slice.rs"]
+mod ref_or_owned_slice;
+
+#[path = "ref_or_owned_future.rs"]
+mod ref_or_owned_future;
+
+#[path = "ref_or_owned_pin.rs"]
+mod ref_or_owned_pin;
+
+pub use ref_or_owned_pin::*;
+
+#[path = "ref_or_owned_error.rs"]
+mod ref_or_owned_error;
+
+pub use ref_or_owned_error::*;
+
+#[path = "ref_or_owned_shared.rs"]
+mod ref_or_owned_shared;
+
+pub use ref_or_owned_shared::*;
+
+#[path = "ref_or_owned_drop.rs"]
+mod ref_or_owned_drop;
+
+pub use ref_or_owned_drop::*;
+
+#[path = "ref_or_owned_try_clone.rs"]
+mod ref_or_owned_try_clone;
+
+pub use ref_or_owned_try_clone::*;
+
+#[path = "ref_or_owned_debug.rs"]
+mod ref_or_owned_debug;
+
+pub use ref_or_owned_debug::DebugPayload;
+use ref_or_owned_debug::Payload;
+
+#[path = "ref_or_owned_iter.rs"]
+mod ref_or_owned_iter;
+
+pub use ref_or_owned_iter::*;
+
 #[cfg(test)]
 #[path = "ref_or_owned_tests.rs"]
 mod ref_or_owned_tests;
@@ -278,3 +722,71 @@ mod ref_or_owned_tests;
 #[path = "ref_or_owned_macros.rs"]
 #[macro_use]
 mod ref_or_owned_macros;
+
+#[cfg(feature = "heapless")]
+#[path = "ref_or_owned_heapless.rs"]
+mod ref_or_owned_heapless;
+
+#[cfg(feature = "heapless")]
+pub use ref_or_owned_heapless::*;
+
+#[cfg(feature = "wasm")]
+#[path = "ref_or_owned_wasm.rs"]
+mod ref_or_owned_wasm;
+
+#[cfg(feature = "memmap2")]
+#[path = "ref_or_owned_mmap.rs"]
+mod ref_or_owned_mmap;
+
+#[cfg(feature = "memmap2")]
+pub use ref_or_owned_mmap::*;
+
+#[cfg(feature = "bytemuck")]
+#[path = "ref_or_owned_bytemuck.rs"]
+mod ref_or_owned_bytemuck;
+
+#[cfg(feature = "defmt")]
+#[path = "ref_or_owned_defmt.rs"]
+mod ref_or_owned_defmt;
+
+#[cfg(feature = "serde")]
+#[path = "ref_or_owned_serde.rs"]
+mod ref_or_owned_serde;
+
+#[cfg(feature = "axum")]
+#[path = "ref_or_owned_axum.rs"]
+mod ref_or_owned_axum;
+
+#[cfg(feature = "actix")]
+#[path = "ref_or_owned_actix.rs"]
+mod ref_or_owned_actix;
+
+#[cfg(feature = "beef")]
+#[path = "ref_or_owned_beef.rs"]
+mod ref_or_owned_beef;
+
+#[cfg(feature = "erased-serde")]
+#[path = "ref_or_owned_erased.rs"]
+mod ref_or_owned_erased;
+
+#[cfg(feature = "erased-serde")]
+pub use ref_or_owned_erased::Erased;
+
+#[cfg(feature = "schemars")]
+#[path = "ref_or_owned_schemars.rs"]
+mod ref_or_owned_schemars;
+
+#[cfg(feature = "arbitrary")]
+#[path = "ref_or_owned_arbitrary.rs"]
+mod ref_or_owned_arbitrary;
+
+#[cfg(feature = "proptest")]
+#[path = "ref_or_owned_proptest.rs"]
+mod ref_or_owned_proptest;
+
+#[cfg(feature = "proptest")]
+pub use ref_or_owned_proptest::*;
+
+#[cfg(feature = "quickcheck")]
+#[path = "ref_or_owned_quickcheck.rs"]
+mod ref_or_owned_quickcheck;
@@ -0,0 +1,48 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Responses from the string and byte specializations of `RefOrBox` in actix-web
+//! handlers. This requires the "actix" feature.
+//!
+//! Borrowed values must live for `'static`, since the response body outlives the
+//! handler. They are then sent without copying. Owned strings and byte slices are
+//! moved into the response without copying either.
+
+use super::RefOrBox;
+use actix_web::body::BoxBody;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+
+impl Responder for RefOrBox<'static, str> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.respond_to(req).map_into_boxed_body(),
+            Self::Owned(owned_value) => String::from(owned_value).respond_to(req).map_into_boxed_body()
+        }
+    }
+}
+
+impl Responder for RefOrBox<'static, [u8]> {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.respond_to(req).map_into_boxed_body(),
+            Self::Owned(owned_value) => Vec::from(owned_value).respond_to(req).map_into_boxed_body()
+        }
+    }
+}
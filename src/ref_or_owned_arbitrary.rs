@@ -0,0 +1,40 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Fuzzing support for the wrappers. This requires the "arbitrary" feature.
+//!
+//! Values are always generated as the Owned variant, since a Borrowed value would need
+//! somewhere to borrow from which outlives the fuzz input.
+
+use super::{RefMutOrOwned, RefOrOwned};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+macro_rules! arbitrary_impls {
+    ($typename:ident) => {
+        impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for $typename<'_, T> {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                T::arbitrary(u).map(Self::Owned)
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                T::size_hint(depth)
+            }
+        }
+    }
+}
+
+arbitrary_impls!(RefOrOwned);
+arbitrary_impls!(RefMutOrOwned);
@@ -0,0 +1,44 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Responses from the string and byte specializations of `RefOrBox` in axum
+//! handlers. This requires the "axum" feature.
+//!
+//! Borrowed values must live for `'static`, since the response body outlives the
+//! handler. They are then sent without copying, as are owned values. Request
+//! bodies are always owned, so the plain `String` and `Bytes` extractors should
+//! be used to receive them.
+
+use super::RefOrBox;
+use axum_core::response::{IntoResponse, Response};
+
+impl IntoResponse for RefOrBox<'static, str> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.into_response(),
+            Self::Owned(owned_value) => owned_value.into_response()
+        }
+    }
+}
+
+impl IntoResponse for RefOrBox<'static, [u8]> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.into_response(),
+            Self::Owned(owned_value) => owned_value.into_response()
+        }
+    }
+}
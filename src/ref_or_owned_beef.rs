@@ -0,0 +1,75 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between the string and slice specializations of `RefOrBox` and the
+//! compact Cows of the beef crate. This requires the "beef" feature.
+//!
+//! A borrowed value stays borrowed and an owned value is moved across, so nothing
+//! is cloned. As with beef itself, an owned value without capacity is treated as
+//! borrowed.
+
+use super::RefOrBox;
+
+macro_rules! beef_conversions {
+    ($cow:ident) => {
+        impl<'t> From<$cow<'t, str>> for RefOrBox<'t, str> {
+            fn from(value: $cow<'t, str>) -> Self {
+                if value.is_borrowed() {
+                    Self::Borrowed(value.unwrap_borrowed())
+                } else {
+                    Self::Owned(value.into_owned().into_boxed_str())
+                }
+            }
+        }
+
+        impl<'t> From<RefOrBox<'t, str>> for $cow<'t, str> {
+            fn from(value: RefOrBox<'t, str>) -> Self {
+                match value {
+                    RefOrBox::Borrowed(borrowed_value) => $cow::borrowed(borrowed_value),
+                    RefOrBox::Owned(owned_box) => $cow::owned(owned_box.into_string())
+                }
+            }
+        }
+
+        impl<'t, T: Clone> From<$cow<'t, [T]>> for RefOrBox<'t, [T]> {
+            fn from(value: $cow<'t, [T]>) -> Self {
+                if value.is_borrowed() {
+                    Self::Borrowed(value.unwrap_borrowed())
+                } else {
+                    Self::Owned(value.into_owned().into_boxed_slice())
+                }
+            }
+        }
+
+        impl<'t, T: Clone> From<RefOrBox<'t, [T]>> for $cow<'t, [T]> {
+            fn from(value: RefOrBox<'t, [T]>) -> Self {
+                match value {
+                    RefOrBox::Borrowed(borrowed_value) => $cow::borrowed(borrowed_value),
+                    RefOrBox::Owned(owned_box) => $cow::owned(owned_box.into_vec())
+                }
+            }
+        }
+    }
+}
+
+use beef::Cow as WideCow;
+beef_conversions!(WideCow);
+
+// beef only provides the lean Cow on 64-bit targets
+#[cfg(target_pointer_width = "64")]
+use beef::lean::Cow as LeanCow;
+#[cfg(target_pointer_width = "64")]
+beef_conversions!(LeanCow);
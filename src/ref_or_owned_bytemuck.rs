@@ -0,0 +1,63 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::RefOrBox;
+use std::mem::{size_of, size_of_val};
+use bytemuck::{Pod, PodCastError};
+
+impl<'t, T: Pod> RefOrBox<'t, [T]> {
+    /// Reinterprets the slice as a slice of another plain-old-data type. This requires
+    /// the "bytemuck" feature.
+    ///
+    /// If the data is borrowed and suitably aligned for `U`, the result borrows the same data.
+    /// If the data is owned and `U` has the same alignment as `T`, the allocation is reused.
+    /// Otherwise, the data is copied into a newly allocated, aligned slice of `U`.
+    ///
+    /// Fails if the size of the slice in bytes is not a multiple of the size of `U`,
+    /// or if exactly one of `T` and `U` is zero-sized.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let bytes: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([1, 0, 2, 0]));
+    /// let words: RefOrBox<[u16]> = bytes.cast().unwrap();
+    /// assert_eq!(&[u16::from_ne_bytes([1, 0]), u16::from_ne_bytes([2, 0])], &*words);
+    /// ```
+    pub fn cast<U: Pod>(self) -> Result<RefOrBox<'t, [U]>, PodCastError> {
+        if size_of::<T>() == 0 || size_of::<U>() == 0 {
+            if size_of::<T>() != size_of::<U>() {
+                return Err(PodCastError::SizeMismatch);
+            }
+        } else if size_of_val::<[T]>(&self) % size_of::<U>() != 0 {
+            return Err(PodCastError::OutputSliceWouldHaveSlop);
+        }
+        match self {
+            Self::Borrowed(borrowed_value) => match bytemuck::try_cast_slice(borrowed_value) {
+                Ok(cast_value) => Ok(RefOrBox::Borrowed(cast_value)),
+                Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned) => {
+                    Ok(RefOrBox::Owned(bytemuck::allocation::pod_collect_to_vec(borrowed_value).into_boxed_slice()))
+                },
+                Err(error) => Err(error)
+            },
+            Self::Owned(owned_box) => match bytemuck::allocation::try_cast_slice_box(owned_box) {
+                Ok(cast_box) => Ok(RefOrBox::Owned(cast_box)),
+                Err((PodCastError::AlignmentMismatch, owned_box)) => {
+                    Ok(RefOrBox::Owned(bytemuck::allocation::pod_collect_to_vec(&owned_box).into_boxed_slice()))
+                },
+                Err((error, _)) => Err(error)
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefMutOrOwned, RefOrBox, RefMutOrBox};
+
+impl<'t, T> RefOrOwned<'t, T> {
+    /// Converts to a `RefOrBox` of the same type. A borrowed value stays borrowed,
+    /// and an owned value is moved into a box.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+    /// let boxed: RefOrBox<u8> = RefOrOwned::Owned(5).into_ref_or_box();
+    /// assert_eq!(RefOrOwned::Owned(5), boxed.into_ref_or_owned());
+    /// ```
+    pub fn into_ref_or_box(self) -> RefOrBox<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrBox::Borrowed(borrowed_value),
+            Self::Owned(owned_value) => RefOrBox::Owned(Box::new(owned_value))
+        }
+    }
+}
+
+impl<'t, T> RefOrBox<'t, T> {
+    /// Converts to a `RefOrOwned` of the same type. A borrowed value stays borrowed,
+    /// and an owned value is moved out of its box.
+    pub fn into_ref_or_owned(self) -> RefOrOwned<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrOwned::Borrowed(borrowed_value),
+            Self::Owned(owned_box) => RefOrOwned::Owned(*owned_box)
+        }
+    }
+}
+
+impl<'t, T> RefMutOrOwned<'t, T> {
+    /// Converts to a `RefMutOrBox` of the same type. A borrowed value stays borrowed,
+    /// and an owned value is moved into a box.
+    pub fn into_ref_mut_or_box(self) -> RefMutOrBox<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefMutOrBox::Borrowed(borrowed_value),
+            Self::Owned(owned_value) => RefMutOrBox::Owned(Box::new(owned_value))
+        }
+    }
+}
+
+impl<'t, T> RefMutOrBox<'t, T> {
+    /// Converts to a `RefMutOrOwned` of the same type. A borrowed value stays borrowed,
+    /// and an owned value is moved out of its box.
+    pub fn into_ref_mut_or_owned(self) -> RefMutOrOwned<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefMutOrOwned::Borrowed(borrowed_value),
+            Self::Owned(owned_box) => RefMutOrOwned::Owned(*owned_box)
+        }
+    }
+}
+
+impl<'t, T> From<RefOrOwned<'t, T>> for RefOrBox<'t, T> {
+    #[inline]
+    fn from(value: RefOrOwned<'t, T>) -> Self {
+        value.into_ref_or_box()
+    }
+}
+
+impl<'t, T> From<RefOrBox<'t, T>> for RefOrOwned<'t, T> {
+    #[inline]
+    fn from(value: RefOrBox<'t, T>) -> Self {
+        value.into_ref_or_owned()
+    }
+}
+
+impl<'t, T> From<RefMutOrOwned<'t, T>> for RefMutOrBox<'t, T> {
+    #[inline]
+    fn from(value: RefMutOrOwned<'t, T>) -> Self {
+        value.into_ref_mut_or_box()
+    }
+}
+
+impl<'t, T> From<RefMutOrBox<'t, T>> for RefMutOrOwned<'t, T> {
+    #[inline]
+    fn from(value: RefMutOrBox<'t, T>) -> Self {
+        value.into_ref_mut_or_owned()
+    }
+}
+
+impl<'t, T> RefMutOrOwned<'t, T> {
+    /// Downgrades to a `RefOrOwned`, for use with read-only APIs. A mutable reference
+    /// is reborrowed as immutable, and an owned value is moved across.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::{RefMutOrOwned, RefOrOwned};
+    /// let mut value = 5;
+    /// let shared: RefOrOwned<u8> = RefMutOrOwned::Borrowed(&mut value).into_shared();
+    /// assert!(shared.is_borrowed());
+    /// ```
+    pub fn into_shared(self) -> RefOrOwned<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrOwned::Borrowed(borrowed_value),
+            Self::Owned(owned_value) => RefOrOwned::Owned(owned_value)
+        }
+    }
+}
+
+impl<'t, T: ?Sized> RefMutOrBox<'t, T> {
+    /// Downgrades to a `RefOrBox`, for use with read-only APIs. A mutable reference
+    /// is reborrowed as immutable, and an owned box is moved across.
+    pub fn into_shared(self) -> RefOrBox<'t, T> {
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrBox::Borrowed(borrowed_value),
+            Self::Owned(owned_box) => RefOrBox::Owned(owned_box)
+        }
+    }
+}
+
+impl<'t, T> From<RefMutOrOwned<'t, T>> for RefOrOwned<'t, T> {
+    #[inline]
+    fn from(value: RefMutOrOwned<'t, T>) -> Self {
+        value.into_shared()
+    }
+}
+
+impl<'t, T: ?Sized> From<RefMutOrBox<'t, T>> for RefOrBox<'t, T> {
+    #[inline]
+    fn from(value: RefMutOrBox<'t, T>) -> Self {
+        value.into_shared()
+    }
+}
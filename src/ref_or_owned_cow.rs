@@ -0,0 +1,82 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions and comparisons between the wrappers and `Cow`. A borrowed value
+//! stays borrowed and an owned value is moved across, so nothing is cloned.
+
+use super::{RefOrOwned, RefOrBox};
+use super::ref_or_owned_macros::deref_eq;
+use std::borrow::Cow;
+use std::ops::Deref;
+
+impl<'t, T: Clone> From<Cow<'t, T>> for RefOrOwned<'t, T> {
+    fn from(value: Cow<'t, T>) -> Self {
+        match value {
+            Cow::Borrowed(borrowed_value) => Self::Borrowed(borrowed_value),
+            Cow::Owned(owned_value) => Self::Owned(owned_value)
+        }
+    }
+}
+
+impl<'t, T: Clone> From<RefOrOwned<'t, T>> for Cow<'t, T> {
+    fn from(value: RefOrOwned<'t, T>) -> Self {
+        match value {
+            RefOrOwned::Borrowed(borrowed_value) => Cow::Borrowed(borrowed_value),
+            RefOrOwned::Owned(owned_value) => Cow::Owned(owned_value)
+        }
+    }
+}
+
+/// The owned value is converted into a box, such as `String` into `Box<str>`.
+impl<'t, T: ?Sized + ToOwned> From<Cow<'t, T>> for RefOrBox<'t, T> where T::Owned: Into<Box<T>> {
+    fn from(value: Cow<'t, T>) -> Self {
+        match value {
+            Cow::Borrowed(borrowed_value) => Self::Borrowed(borrowed_value),
+            Cow::Owned(owned_value) => Self::Owned(owned_value.into())
+        }
+    }
+}
+
+/// The owned box is converted into the owned counterpart, such as `Box<str>` into `String`.
+impl<'t, T: ?Sized + ToOwned> From<RefOrBox<'t, T>> for Cow<'t, T> where T::Owned: From<Box<T>> {
+    fn from(value: RefOrBox<'t, T>) -> Self {
+        match value {
+            RefOrBox::Borrowed(borrowed_value) => Cow::Borrowed(borrowed_value),
+            RefOrBox::Owned(owned_box) => Cow::Owned(T::Owned::from(owned_box))
+        }
+    }
+}
+
+macro_rules! cow_partial_eq_impls {
+    ($typename:ident<T $(: ?$sized:ident)?>) => {
+        impl<T: PartialEq<U> $(+ ?$sized)?, U: ?Sized + ToOwned> PartialEq<Cow<'_, U>> for $typename<'_, T> {
+            #[inline]
+            fn eq(&self, other: &Cow<'_, U>) -> bool {
+                deref_eq(self.deref(), other.deref())
+            }
+        }
+
+        impl<T: $(?$sized +)? PartialEq<U>, U: ?Sized + ToOwned> PartialEq<$typename<'_, T>> for Cow<'_, U> {
+            #[inline]
+            fn eq(&self, other: &$typename<'_, T>) -> bool {
+                deref_eq(other.deref(), self.deref())
+            }
+        }
+    }
+}
+
+cow_partial_eq_impls!(RefOrOwned<T>);
+cow_partial_eq_impls!(RefOrBox<T: ?Sized>);
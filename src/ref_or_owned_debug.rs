@@ -0,0 +1,86 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::{Debug, Formatter};
+
+/// Formats the values held by `RefOrBox` and `RefMutOrBox` for `Debug`.
+///
+/// Every `Debug` type implements this trait by formatting itself. Trait objects which
+/// are not `Debug` can implement it with the `impl_opaque_debug!` macro, which formats
+/// them by their type name. Then structs holding the wrappers can still derive `Debug`.
+///
+/// ```rust
+/// # use polymorph::impl_opaque_debug;
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// trait Handler {
+///     fn handle(&self);
+/// }
+///
+/// impl_opaque_debug!(Handler);
+///
+/// #[derive(Debug)]
+/// struct Route<'h> {
+///     path: &'static str,
+///     handler: RefOrBox<'h, dyn Handler>
+/// }
+///
+/// struct Noop;
+///
+/// impl Handler for Noop {
+///     fn handle(&self) {}
+/// }
+///
+/// let route = Route { path: "/", handler: RefOrBox::Borrowed(&Noop) };
+/// // Route { path: "/", handler: Borrowed(dyn my_crate::Handler) }
+/// assert!(format!("{:?}", route).contains("Handler"));
+/// ```
+pub trait DebugPayload {
+    /// Formats the value
+    fn fmt_payload(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+}
+
+impl<T: ?Sized + Debug> DebugPayload for T {
+    #[inline]
+    fn fmt_payload(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt(f)
+    }
+}
+
+/// Formats a payload through `DebugPayload`, so that it can be passed to `debug_tuple`.
+pub(crate) struct Payload<'t, T: ?Sized>(pub(crate) &'t T);
+
+impl<T: ?Sized + DebugPayload> Debug for Payload<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_payload(f)
+    }
+}
+
+///
+/// Implements `DebugPayload` for a trait object whose trait is not `Debug`, so that
+/// `RefOrBox<dyn Trait>` and `RefMutOrBox<dyn Trait>` implement `Debug`.
+///
+/// The trait object is formatted by its type name, such as `Borrowed(dyn my_crate::Trait)`.
+///
+#[macro_export]
+macro_rules! impl_opaque_debug {
+    ($trait:path) => {
+        impl $crate::ref_or_owned::DebugPayload for dyn $trait + '_ {
+            fn fmt_payload(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(::std::any::type_name::<Self>())
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Implementations of `defmt::Format`, which forward to the wrapped value.
+//! This requires the "defmt" feature.
+
+use super::{RefOrOwned, RefMutOrOwned, RefOrBox, RefMutOrBox, ArcOrOwned, RcOrOwned};
+use std::ops::Deref;
+use defmt::{Format, Formatter};
+
+impl<T: Format> Format for RefOrOwned<'_, T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
+
+impl<T: Format> Format for RefMutOrOwned<'_, T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
+
+impl<T: ?Sized + Format> Format for RefOrBox<'_, T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
+
+impl<T: ?Sized + Format> Format for RefMutOrBox<'_, T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
+
+impl<T: Format> Format for ArcOrOwned<T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
+
+impl<T: Format> Format for RcOrOwned<T> {
+    fn format(&self, f: Formatter<'_>) {
+        self.deref().format(f)
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::IntoVariant;
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+/// Wraps one of the wrapper types together with a finalizer, which runs when the
+/// wrapper is dropped while holding the Owned variant.
+///
+/// The finalizer receives the owned value, so that it may be returned to a pool or
+/// flushed before it goes away. Nothing happens if the wrapper is Borrowed, since
+/// the value belongs to someone else.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{OnDropOwned, RefOrOwned};
+/// use std::cell::RefCell;
+///
+/// let pool = RefCell::new(Vec::new());
+/// let shared = vec![0u8; 16];
+///
+/// let borrowed = OnDropOwned::new(RefOrOwned::Borrowed(&shared), |buffer| pool.borrow_mut().push(buffer));
+/// let owned = OnDropOwned::new(RefOrOwned::Owned(vec![1u8; 16]), |buffer| pool.borrow_mut().push(buffer));
+/// drop(borrowed);
+/// drop(owned);
+/// assert_eq!(vec![vec![1u8; 16]], *pool.borrow());
+/// ```
+pub struct OnDropOwned<W: IntoVariant, F: FnOnce(W::Value)> {
+    wrapper: Option<W>,
+    finalizer: Option<F>
+}
+
+impl<W: IntoVariant, F: FnOnce(W::Value)> OnDropOwned<W, F> {
+    /// Attaches the finalizer to the wrapper
+    pub fn new(wrapper: W, finalizer: F) -> Self {
+        Self {
+            wrapper: Some(wrapper),
+            finalizer: Some(finalizer)
+        }
+    }
+
+    /// Detaches the finalizer without running it, and returns the wrapper
+    pub fn into_inner(mut self) -> W {
+        self.finalizer = None;
+        self.wrapper.take().expect("Wrapper is only taken on drop")
+    }
+}
+
+impl<W: IntoVariant, F: FnOnce(W::Value)> Deref for OnDropOwned<W, F> {
+    type Target = W;
+
+    fn deref(&self) -> &Self::Target {
+        self.wrapper.as_ref().expect("Wrapper is only taken on drop")
+    }
+}
+
+impl<W: IntoVariant, F: FnOnce(W::Value)> DerefMut for OnDropOwned<W, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.wrapper.as_mut().expect("Wrapper is only taken on drop")
+    }
+}
+
+impl<W: IntoVariant + Debug, F: FnOnce(W::Value)> Debug for OnDropOwned<W, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnDropOwned")
+            .field("wrapper", &self.wrapper)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: IntoVariant, F: FnOnce(W::Value)> Drop for OnDropOwned<W, F> {
+    fn drop(&mut self) {
+        if let (Some(wrapper), Some(finalizer)) = (self.wrapper.take(), self.finalizer.take()) {
+            if let Ok(owned_value) = wrapper.into_variant() {
+                finalizer(owned_value);
+            }
+        }
+    }
+}
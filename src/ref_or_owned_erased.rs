@@ -0,0 +1,69 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Serialization of trait objects through erased-serde. This requires the
+//! "erased-serde" feature.
+//!
+//! Traits with `erased_serde::Serialize` as a supertrait are object-safe, but their
+//! trait objects do not implement `serde::Serialize` by themselves. The wrappers can
+//! lend them out as `Erased`, which does.
+//!
+//! Tagged trait objects are deserialized with the help of a `Registry`.
+
+use super::{RefMutOrBox, RefOrBox};
+use std::ops::Deref;
+use serde::{Serialize, Serializer};
+
+/// A borrowed `erased_serde::Serialize` value, which implements `serde::Serialize`.
+///
+/// This is obtained from `RefOrBox::as_erased` or `RefMutOrBox::as_erased`.
+#[derive(Copy, Clone)]
+pub struct Erased<'t, T: ?Sized>(pub &'t T);
+
+impl<T: ?Sized + erased_serde::Serialize> Serialize for Erased<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        erased_serde::serialize(self.0, serializer)
+    }
+}
+
+macro_rules! erased_serde_impls {
+    ($typename:ident) => {
+        impl<T: ?Sized + erased_serde::Serialize> $typename<'_, T> {
+            /// Lends out the value as a serializable trait object.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// trait Event: erased_serde::Serialize {}
+            /// impl Event for u32 {}
+            /// impl Event for String {}
+            ///
+            /// let events: Vec<RefOrBox<dyn Event>> = vec![
+            ///     RefOrBox::Owned(Box::new(1_u32)),
+            ///     RefOrBox::Owned(Box::new(String::from("two")))
+            /// ];
+            /// let erased: Vec<_> = events.iter().map(|event| event.as_erased()).collect();
+            /// assert_eq!("[1,\"two\"]", serde_json::to_string(&erased).unwrap());
+            /// ```
+            pub fn as_erased(&self) -> Erased<'_, T> {
+                Erased(self.deref())
+            }
+        }
+    }
+}
+
+erased_serde_impls!(RefOrBox);
+
+erased_serde_impls!(RefMutOrBox);
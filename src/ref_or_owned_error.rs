@@ -0,0 +1,50 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefOrBox};
+use super::ref_or_owned_macros::*;
+use std::error::Error;
+
+/// An iterator over an error and its chain of sources.
+///
+/// Created by `RefOrBox::chain`. The first item is the wrapped error itself.
+#[derive(Debug, Clone)]
+pub struct ErrorChain<'e> {
+    next: Option<&'e (dyn Error + 'static)>
+}
+
+impl<'e> ErrorChain<'e> {
+    /// Iterates over the given error and its sources
+    pub fn new(error: &'e (dyn Error + 'static)) -> Self {
+        Self { next: Some(error) }
+    }
+}
+
+impl<'e> Iterator for ErrorChain<'e> {
+    type Item = &'e (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+impl std::iter::FusedIterator for ErrorChain<'_> {}
+
+error_downcast_impls!(dyn Error + 'static; Error);
+error_downcast_impls!(dyn Error + Send + 'static; Error + Send);
+error_downcast_impls!(dyn Error + Send + Sync + 'static; Error + Send + Sync);
@@ -0,0 +1,41 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefOrBox};
+use std::future::IntoFuture;
+
+impl<F: IntoFuture + Clone> IntoFuture for RefOrOwned<'_, F> {
+    type Output = F::Output;
+    type IntoFuture = F::IntoFuture;
+
+    /// Converts the owned value into a future, or clones the borrowed value first.
+    fn into_future(self) -> Self::IntoFuture {
+        self.into_owned().into_future()
+    }
+}
+
+impl<F: IntoFuture + Clone> IntoFuture for RefOrBox<'_, F> {
+    type Output = F::Output;
+    type IntoFuture = F::IntoFuture;
+
+    /// Converts the owned value into a future, or clones the borrowed value first.
+    fn into_future(self) -> Self::IntoFuture {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.clone().into_future(),
+            Self::Owned(owned_value) => (*owned_value).into_future()
+        }
+    }
+}
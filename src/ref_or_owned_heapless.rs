@@ -0,0 +1,283 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Deref;
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// A type which can be either a borrowed string slice, or an owned fixed-capacity
+/// string. This requires the "heapless" feature.
+///
+/// The owned variant is a `heapless::String<N>`, whose storage is inline.
+/// Thus, neither variant requires allocation, which makes `StrOrHeapless`
+/// suitable for firmware and other allocation-free environments.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::StrOrHeapless;
+/// fn greeting(name: Option<&str>) -> StrOrHeapless<'static, 16> {
+///     match name {
+///         None => StrOrHeapless::Borrowed("Hello, world"),
+///         Some(_) => {
+///             let mut owned = heapless::String::new();
+///             owned.push_str("Hello, friend").unwrap();
+///             StrOrHeapless::Owned(owned)
+///         }
+///     }
+/// }
+/// assert_eq!("Hello, world", &*greeting(None));
+/// ```
+#[derive(Debug)]
+pub enum StrOrHeapless<'t, const N: usize> {
+    Borrowed(&'t str),
+    Owned(heapless::String<N>)
+}
+
+impl<'t, const N: usize> From<&'t str> for StrOrHeapless<'t, N> {
+    fn from(value: &'t str) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<const N: usize> From<heapless::String<N>> for StrOrHeapless<'_, N> {
+    fn from(value: heapless::String<N>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<const N: usize> Default for StrOrHeapless<'_, N> {
+    fn default() -> Self {
+        Self::Owned(heapless::String::new())
+    }
+}
+
+impl<const N: usize> Deref for StrOrHeapless<'_, N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value,
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl<'t, const N: usize> StrOrHeapless<'t, N> {
+    /// Obtains an owned fixed-capacity string.
+    ///
+    /// If the data is borrowed, it will be copied into a new `heapless::String<N>`.
+    /// If the data is owned, the owned value will be moved out.
+    ///
+    /// Because the owned storage has a fixed capacity, copying may fail. In that
+    /// case, the borrowed string slice is returned as the error.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::StrOrHeapless;
+    /// let fits: StrOrHeapless<8> = StrOrHeapless::Borrowed("short");
+    /// assert_eq!("short", fits.into_owned().unwrap().as_str());
+    ///
+    /// let too_long: StrOrHeapless<4> = StrOrHeapless::Borrowed("too long");
+    /// assert_eq!(Err("too long"), too_long.into_owned());
+    /// ```
+    pub fn into_owned(self) -> Result<heapless::String<N>, &'t str> {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                heapless::String::try_from(borrowed_value).map_err(|()| borrowed_value)
+            },
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+}
+
+impl<const N: usize> AsRef<str> for StrOrHeapless<'_, N> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.deref()
+    }
+}
+
+impl<const N: usize> Borrow<str> for StrOrHeapless<'_, N> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.deref()
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<StrOrHeapless<'_, M>> for StrOrHeapless<'_, N> {
+    #[inline]
+    fn eq(&self, other: &StrOrHeapless<'_, M>) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl<const N: usize> Eq for StrOrHeapless<'_, N> {}
+
+impl<const N: usize, const M: usize> PartialOrd<StrOrHeapless<'_, M>> for StrOrHeapless<'_, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &StrOrHeapless<'_, M>) -> Option<Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<const N: usize> Ord for StrOrHeapless<'_, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<const N: usize> Hash for StrOrHeapless<'_, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl<const N: usize> Display for StrOrHeapless<'_, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// A type which can be either a borrowed slice, or an owned fixed-capacity
+/// vector. This requires the "heapless" feature.
+///
+/// The owned variant is a `heapless::Vec<T, N>`, whose storage is inline.
+/// Thus, neither variant requires allocation, which makes `SliceOrHeaplessVec`
+/// suitable for firmware and other allocation-free environments.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::SliceOrHeaplessVec;
+/// static DEFAULT_READINGS: [u16; 3] = [0, 0, 0];
+///
+/// fn readings(sensor_ready: bool) -> SliceOrHeaplessVec<'static, u16, 8> {
+///     if sensor_ready {
+///         let mut owned = heapless::Vec::new();
+///         owned.push(512).unwrap();
+///         SliceOrHeaplessVec::Owned(owned)
+///     } else {
+///         SliceOrHeaplessVec::Borrowed(&DEFAULT_READINGS)
+///     }
+/// }
+/// assert_eq!(3, readings(false).len());
+/// ```
+#[derive(Debug)]
+pub enum SliceOrHeaplessVec<'t, T: 't, const N: usize> {
+    Borrowed(&'t [T]),
+    Owned(heapless::Vec<T, N>)
+}
+
+impl<'t, T, const N: usize> From<&'t [T]> for SliceOrHeaplessVec<'t, T, N> {
+    fn from(value: &'t [T]) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<T, const N: usize> From<heapless::Vec<T, N>> for SliceOrHeaplessVec<'_, T, N> {
+    fn from(value: heapless::Vec<T, N>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<T, const N: usize> Default for SliceOrHeaplessVec<'_, T, N> {
+    fn default() -> Self {
+        Self::Owned(heapless::Vec::new())
+    }
+}
+
+impl<T, const N: usize> Deref for SliceOrHeaplessVec<'_, T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value,
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl<'t, T, const N: usize> SliceOrHeaplessVec<'t, T, N> where T: Clone {
+    /// Obtains an owned fixed-capacity vector.
+    ///
+    /// If the data is borrowed, its elements will be cloned into a new `heapless::Vec<T, N>`.
+    /// If the data is owned, the owned value will be moved out.
+    ///
+    /// Because the owned storage has a fixed capacity, cloning may fail. In that
+    /// case, the borrowed slice is returned as the error.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::SliceOrHeaplessVec;
+    /// let data = [1, 2, 3];
+    /// let fits: SliceOrHeaplessVec<u8, 4> = SliceOrHeaplessVec::Borrowed(&data);
+    /// assert_eq!(&[1, 2, 3], fits.into_owned().unwrap().as_slice());
+    ///
+    /// let too_long: SliceOrHeaplessVec<u8, 2> = SliceOrHeaplessVec::Borrowed(&data);
+    /// assert!(too_long.into_owned().is_err());
+    /// ```
+    pub fn into_owned(self) -> Result<heapless::Vec<T, N>, &'t [T]> {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                heapless::Vec::from_slice(borrowed_value).map_err(|()| borrowed_value)
+            },
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.deref()
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self.deref()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<SliceOrHeaplessVec<'_, U, M>> for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn eq(&self, other: &SliceOrHeaplessVec<'_, U, M>) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SliceOrHeaplessVec<'_, T, N> {}
+
+impl<T: PartialOrd, const N: usize, const M: usize> PartialOrd<SliceOrHeaplessVec<'_, T, M>> for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn partial_cmp(&self, other: &SliceOrHeaplessVec<'_, T, M>) -> Option<Ordering> {
+        self.deref().partial_cmp(other.deref())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for SliceOrHeaplessVec<'_, T, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrBox, RefOrOwned};
+use std::iter::FusedIterator;
+
+/// An iterator over the elements of a wrapped collection, each wrapped in turn.
+///
+/// Elements of a Borrowed collection are Borrowed, while elements of an Owned collection
+/// are moved out as Owned. Created by the `IntoIterator` implementations for
+/// `RefOrOwned<Vec<T>>` and `RefOrBox<[T]>`.
+///
+/// Before these implementations existed, `into_iter` on a `RefOrBox<[T]>` went through
+/// deref and yielded `&T`. To iterate over plain references, call `.iter()`, which still
+/// dereferences to the slice and yields `&T`.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+/// let shared = vec![String::from("a"), String::from("b")];
+/// for element in RefOrOwned::Borrowed(&shared) {
+///     assert!(element.is_borrowed());
+/// }
+///
+/// let owned: RefOrBox<[String]> = RefOrBox::Owned(shared.into_boxed_slice());
+/// let elements: Vec<String> = owned.into_iter().map(RefOrOwned::into_owned).collect();
+/// assert_eq!(vec!["a", "b"], elements);
+///
+/// let numbers: RefOrBox<[u32]> = RefOrBox::Borrowed(&[1, 2, 3]);
+/// let total: u32 = numbers.iter().sum();
+/// assert_eq!(6, total);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElementIter<'t, T> {
+    elements: Elements<'t, T>
+}
+
+#[derive(Debug, Clone)]
+enum Elements<'t, T> {
+    Borrowed(std::slice::Iter<'t, T>),
+    Owned(std::vec::IntoIter<T>)
+}
+
+impl<'t, T> ElementIter<'t, T> {
+    fn borrowed(elements: std::slice::Iter<'t, T>) -> Self {
+        Self { elements: Elements::Borrowed(elements) }
+    }
+
+    fn owned(elements: std::vec::IntoIter<T>) -> Self {
+        Self { elements: Elements::Owned(elements) }
+    }
+}
+
+impl<'t, T> Iterator for ElementIter<'t, T> {
+    type Item = RefOrOwned<'t, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.elements {
+            Elements::Borrowed(elements) => elements.next().map(RefOrOwned::Borrowed),
+            Elements::Owned(elements) => elements.next().map(RefOrOwned::Owned)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.elements {
+            Elements::Borrowed(elements) => elements.size_hint(),
+            Elements::Owned(elements) => elements.size_hint()
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for ElementIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.elements {
+            Elements::Borrowed(elements) => elements.next_back().map(RefOrOwned::Borrowed),
+            Elements::Owned(elements) => elements.next_back().map(RefOrOwned::Owned)
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for ElementIter<'_, T> {}
+
+impl<T> FusedIterator for ElementIter<'_, T> {}
+
+impl<'t, T> IntoIterator for RefOrOwned<'t, Vec<T>> {
+    type Item = RefOrOwned<'t, T>;
+    type IntoIter = ElementIter<'t, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::Borrowed(borrowed_value) => ElementIter::borrowed(borrowed_value.iter()),
+            Self::Owned(owned_value) => ElementIter::owned(owned_value.into_iter())
+        }
+    }
+}
+
+impl<'t, T> IntoIterator for RefOrBox<'t, [T]> {
+    type Item = RefOrOwned<'t, T>;
+    type IntoIter = ElementIter<'t, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::Borrowed(borrowed_value) => ElementIter::borrowed(borrowed_value.iter()),
+            Self::Owned(owned_value) => ElementIter::owned(owned_value.into_vec().into_iter())
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RefOrOwned<'_, Vec<T>> {
+    /// Collects the elements into the Owned variant.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::Owned(iter.into_iter().collect())
+    }
+}
+
+impl<T> FromIterator<T> for RefOrBox<'_, [T]> {
+    /// Collects the elements into the Owned variant.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// fn evens(limit: u32) -> RefOrBox<'static, [u32]> {
+    ///     if limit == 0 {
+    ///         return RefOrBox::Borrowed(&[]);
+    ///     }
+    ///     (0..limit).filter(|n| n % 2 == 0).collect()
+    /// }
+    ///
+    /// assert_eq!([0, 2, 4], *evens(5));
+    /// assert!(evens(0).is_borrowed());
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::Owned(iter.into_iter().collect())
+    }
+}
@@ -14,6 +14,85 @@
  * limitations under the License.
  */
 
+/// Compares two dereferenced values, as all `PartialEq` implementations of the wrappers do.
+///
+/// With the "fast-ptr-eq" feature, values at the same address and of the same non-zero size
+/// are considered equal without calling `PartialEq::eq`.
+#[inline]
+pub(crate) fn deref_eq<T: ?Sized + PartialEq<U>, U: ?Sized>(lhs: &T, rhs: &U) -> bool {
+    same_address(lhs, rhs) || lhs.eq(rhs)
+}
+
+/// The counterpart of `deref_eq`, forwarding to `PartialEq::ne` where the values are compared.
+#[inline]
+pub(crate) fn deref_ne<T: ?Sized + PartialEq<U>, U: ?Sized>(lhs: &T, rhs: &U) -> bool {
+    !same_address(lhs, rhs) && lhs.ne(rhs)
+}
+
+/// Whether two references are the same, comparing their metadata, such as the vtable of
+/// a trait object, along with the address. Values of zero-sized types share an address,
+/// so the address alone does not tell them apart.
+#[allow(unknown_lints, ambiguous_wide_pointer_comparisons)]
+#[inline]
+pub(crate) fn same_reference<T: ?Sized>(lhs: &T, rhs: &T) -> bool {
+    std::ptr::eq(lhs, rhs)
+}
+
+#[cfg(feature = "fast-ptr-eq")]
+#[inline]
+fn same_address<T: ?Sized, U: ?Sized>(lhs: &T, rhs: &U) -> bool {
+    let size = std::mem::size_of_val(lhs);
+    size != 0 && size == std::mem::size_of_val(rhs)
+        && std::ptr::eq((lhs as *const T).cast::<u8>(), (rhs as *const U).cast::<u8>())
+}
+
+#[cfg(not(feature = "fast-ptr-eq"))]
+#[inline(always)]
+fn same_address<T: ?Sized, U: ?Sized>(_lhs: &T, _rhs: &U) -> bool {
+    false
+}
+
+/// Formats a variant of a wrapper for `Debug`, as `Variant(value)`.
+///
+/// With the "transparent-debug" feature, only the value is formatted, as with `Display`.
+pub(crate) fn debug_variant<T: ?Sized + std::fmt::Debug>(f: &mut std::fmt::Formatter<'_>, variant: &str, value: &T) -> std::fmt::Result {
+    if cfg!(feature = "transparent-debug") {
+        value.fmt(f)
+    } else {
+        f.debug_tuple(variant).field(&value).finish()
+    }
+}
+
+macro_rules! formatting_impls {
+    ($typename:ident; $($fmt_trait:ident),+) => {
+        $(
+            impl<T: std::fmt::$fmt_trait> std::fmt::$fmt_trait for $typename<'_, T> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::$fmt_trait::fmt(self.deref(), f)
+                }
+            }
+        )+
+    };
+    (?Sized $typename:ident; $($fmt_trait:ident),+) => {
+        $(
+            impl<T: ?Sized + std::fmt::$fmt_trait> std::fmt::$fmt_trait for $typename<'_, T> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::$fmt_trait::fmt(self.deref(), f)
+                }
+            }
+        )+
+    };
+    (shared $typename:ident; $($fmt_trait:ident),+) => {
+        $(
+            impl<T: std::fmt::$fmt_trait> std::fmt::$fmt_trait for $typename<T> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::$fmt_trait::fmt(self.deref(), f)
+                }
+            }
+        )+
+    }
+}
+
 macro_rules! ref_or_owned_impls {
     ($typename:ident) => {
         impl<T: Default> Default for $typename<'_, T> {
@@ -22,6 +101,45 @@ macro_rules! ref_or_owned_impls {
             }
         }
 
+        impl<T: Deref> $typename<'_, T> {
+            /// Views the value through its `Deref` target, as with `Option::as_deref`.
+            /// The result borrows from this wrapper, so nothing is cloned.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+            /// fn shout(text: RefOrBox<'_, str>) -> String {
+            ///     text.to_uppercase()
+            /// }
+            ///
+            /// let owned: RefOrOwned<String> = RefOrOwned::Owned(String::from("text"));
+            /// assert_eq!("TEXT", shout(owned.as_deref()));
+            /// ```
+            pub fn as_deref(&self) -> RefOrBox<'_, T::Target> {
+                RefOrBox::Borrowed(self.deref().deref())
+            }
+        }
+
+        impl<T: Default> $typename<'_, T> {
+            /// Replaces the wrapper with an owned default value, returning the previous
+            /// wrapper, as with `std::mem::take`.
+            ///
+            /// Named so as not to shadow `take` methods on the wrapped value, such as
+            /// `Option::take`, which remain reachable through deref.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// let mut state: RefOrOwned<Vec<u8>> = RefOrOwned::Owned(vec![1, 2]);
+            /// let previous = state.take_wrapper();
+            ///
+            /// assert_eq!(vec![1, 2], *previous);
+            /// assert!(state.is_empty());
+            /// ```
+            #[inline]
+            pub fn take_wrapper(&mut self) -> Self {
+                std::mem::take(self)
+            }
+        }
+
         impl<T> Deref for $typename<'_, T> {
             type Target = T;
 
@@ -39,6 +157,100 @@ macro_rules! ref_or_owned_impls {
             }
         }
 
+        impl<T> $typename<'_, T> {
+            /// Lends out a Borrowed `RefOrOwned` pointing into this wrapper, without cloning.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// fn consume(value: RefOrOwned<'_, String>) -> usize {
+            ///     value.len()
+            /// }
+            ///
+            /// let wrapper: RefOrOwned<String> = RefOrOwned::Owned(String::from("owned"));
+            /// assert_eq!(5, consume(wrapper.as_borrowed()));
+            /// assert_eq!(5, consume(wrapper.as_borrowed()));
+            /// ```
+            pub fn as_borrowed(&self) -> RefOrOwned<'_, T> {
+                RefOrOwned::Borrowed(self.deref())
+            }
+
+            /// Whether both wrappers point to the same value, regardless of whether the value
+            /// is borrowed or owned. Owned values live inside the wrapper, so they are only
+            /// identical to themselves and to the references lent out from them.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// let value = String::from("value");
+            /// let borrowed = RefOrOwned::Borrowed(&value);
+            /// assert!(borrowed.ptr_eq(&RefOrOwned::Borrowed(&value)));
+            /// assert!(!borrowed.ptr_eq(&RefOrOwned::Owned(value.clone())));
+            /// ```
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                std::ptr::eq(self.deref(), other.deref())
+            }
+
+            /// The borrowed reference, if the value is borrowed
+            pub fn borrowed(&self) -> Option<&T> {
+                match self {
+                    Self::Borrowed(borrowed_value) => Some(borrowed_value),
+                    Self::Owned(_) => None
+                }
+            }
+
+            /// A reference to the owned value, if the value is owned
+            pub fn owned_ref(&self) -> Option<&T> {
+                match self {
+                    Self::Borrowed(_) => None,
+                    Self::Owned(owned_value) => Some(owned_value)
+                }
+            }
+
+            /// Moves out the owned value, if the value is owned. Unlike `into_owned`,
+            /// this does not require `Clone`.
+            #[inline]
+            pub fn into_owned_opt(self) -> Option<T> {
+                self.try_into_owned().ok()
+            }
+
+            /// Replaces the wrapper with the given owned value, returning the previous
+            /// wrapper, as with `std::mem::replace`. Like `take_wrapper`, this leaves
+            /// `replace` methods on the wrapped value reachable through deref.
+            #[inline]
+            pub fn replace_wrapper(&mut self, value: T) -> Self {
+                std::mem::replace(self, Self::Owned(value))
+            }
+
+            /// Obtains an owned value of T, computing one from the borrowed value if necessary.
+            ///
+            /// This is like `into_owned`, but permits promotion strategies other than `Clone`.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// let shared = vec![1, 2, 3];
+            /// let wrapper = RefOrOwned::Borrowed(&shared);
+            /// let prefix = wrapper.owned_or_else(|list| list[..1].to_vec());
+            /// assert_eq!(vec![1], prefix);
+            /// ```
+            pub fn owned_or_else<F: FnOnce(&T) -> T>(self, promote: F) -> T {
+                match self {
+                    Self::Borrowed(borrowed_value) => promote(borrowed_value),
+                    Self::Owned(owned_value) => owned_value
+                }
+            }
+
+            /// Acquires a mutable reference to the owned value. If the data is borrowed, an
+            /// owned value is first computed from it, and the wrapper becomes Owned.
+            pub fn get_or_insert_owned<F: FnOnce(&T) -> T>(&mut self, promote: F) -> &mut T {
+                if let Self::Borrowed(borrowed_value) = self {
+                    *self = Self::Owned(promote(borrowed_value));
+                }
+                match self {
+                    Self::Owned(owned_value) => owned_value,
+                    Self::Borrowed(_) => unreachable!("Borrowed value was replaced")
+                }
+            }
+        }
+
         impl<T> $typename<'_, T> where T: Clone {
             /// Obtains an owned value of T.
             ///
@@ -64,6 +276,49 @@ macro_rules! ref_or_owned_impls {
                    Self::Owned(owned_value) => owned_value
                }
             }
+
+            /// Creates a detached owned duplicate, leaving this wrapper untouched.
+            ///
+            /// The result is always the Owned variant, so it does not borrow from
+            /// whatever this wrapper may be borrowing.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// let shared = vec![1, 2];
+            /// let wrapper = RefOrOwned::Borrowed(&shared);
+            /// let snapshot = wrapper.cloned();
+            /// assert!(snapshot.is_owned());
+            /// assert!(wrapper.is_borrowed());
+            /// ```
+            pub fn cloned(&self) -> Self {
+                Self::Owned(self.deref().clone())
+            }
+        }
+
+        impl<T: Clone + 'static> $typename<'_, T> {
+            /// Erases the lifetime, by cloning a borrowed value or moving an owned one.
+            ///
+            /// This is needed to store a value captured from a short-lived borrow in a
+            /// long-lived structure, or to send it to another thread.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrOwned;
+            /// fn store(value: RefOrOwned<'static, String>, storage: &mut Vec<RefOrOwned<'static, String>>) {
+            ///     storage.push(value);
+            /// }
+            ///
+            /// let mut storage = Vec::new();
+            /// let temporary = String::from("temporary");
+            /// store(RefOrOwned::Borrowed(&temporary).into_static(), &mut storage);
+            /// drop(temporary);
+            /// assert_eq!("temporary", *storage[0]);
+            /// ```
+            pub fn into_static(self) -> $typename<'static, T> {
+                match self {
+                    Self::Borrowed(borrowed_value) => $typename::Owned(borrowed_value.clone()),
+                    Self::Owned(owned_value) => $typename::Owned(owned_value)
+                }
+            }
         }
 
         impl<T> AsRef<T> for $typename<'_, T> {
@@ -80,15 +335,16 @@ macro_rules! ref_or_owned_impls {
             }
         }
 
+        #[allow(clippy::partialeq_ne_impl)]
         impl<T: PartialEq<U>, U> PartialEq<$typename<'_, U>> for $typename<'_, T> {
             #[inline]
             fn eq(&self, other: &$typename<'_, U>) -> bool {
-               self.deref().eq(other.deref())
+               deref_eq(self.deref(), other.deref())
             }
 
             #[inline]
             fn ne(&self, other: &$typename<'_, U>) -> bool {
-                self.deref().ne(other.deref())
+               deref_ne(self.deref(), other.deref())
             }
         }
 
@@ -120,6 +376,25 @@ macro_rules! ref_or_owned_impls {
                 self.deref().fmt(f)
             }
         }
+
+        formatting_impls!($typename; LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp);
+
+        impl<T> std::fmt::Pointer for $typename<'_, T> {
+            /// Formats the address of the value, whether borrowed or owned. Owned values live
+            /// inside the wrapper, so their address changes when the wrapper is moved.
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Pointer::fmt(&(self.deref() as *const T).cast::<()>(), f)
+            }
+        }
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for $typename<'_, T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Borrowed(borrowed_value) => debug_variant(f, "Borrowed", borrowed_value),
+                    Self::Owned(owned_value) => debug_variant(f, "Owned", owned_value)
+                }
+            }
+        }
     }
 }
 
@@ -143,6 +418,109 @@ macro_rules! ref_or_box_impls {
             }
         }
 
+        impl<T: ?Sized> $typename<'_, T> {
+            /// Lends out a Borrowed `RefOrBox` pointing into this wrapper, without cloning.
+            pub fn as_borrowed(&self) -> RefOrBox<'_, T> {
+                RefOrBox::Borrowed(self.deref())
+            }
+
+            /// Whether both wrappers point to the same value: the same referent if borrowed,
+            /// or the same box if owned. Only the addresses are compared, so trait objects
+            /// pointing to the same value through different vtables are still identical.
+            ///
+            /// Zero-sized values are the exception, because every box of a zero-sized type
+            /// has the same address. Such a value is identical to another only if both are
+            /// borrowed through the same reference, vtable included, or if both wrappers are
+            /// one and the same. An owned zero-sized value is therefore not identical to the
+            /// references lent out from it.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// let borrowed: RefOrBox<str> = RefOrBox::Borrowed("value");
+            /// let owned: RefOrBox<str> = RefOrBox::Owned(Box::from("value"));
+            /// assert!(borrowed.ptr_eq(&borrowed.as_borrowed()));
+            /// assert!(owned.ptr_eq(&owned.as_borrowed()));
+            /// assert!(!borrowed.ptr_eq(&owned));
+            /// ```
+            pub fn ptr_eq(&self, other: &Self) -> bool {
+                if std::mem::size_of_val(self.deref()) != 0 {
+                    return std::ptr::eq((self.deref() as *const T).cast::<u8>(), (other.deref() as *const T).cast::<u8>());
+                }
+                match (self, other) {
+                    (Self::Borrowed(lhs), Self::Borrowed(rhs)) => same_reference::<T>(lhs, rhs),
+                    _ => std::ptr::eq(self, other)
+                }
+            }
+
+            /// The borrowed reference, if the value is borrowed
+            pub fn borrowed(&self) -> Option<&T> {
+                match self {
+                    Self::Borrowed(borrowed_value) => Some(borrowed_value),
+                    Self::Owned(_) => None
+                }
+            }
+
+            /// A reference to the owned value, if the value is owned
+            pub fn owned_ref(&self) -> Option<&T> {
+                match self {
+                    Self::Borrowed(_) => None,
+                    Self::Owned(owned_value) => Some(owned_value)
+                }
+            }
+
+            /// Moves out the owned value, if the value is owned. Unlike `into_owned`,
+            /// this does not require `Clone`.
+            #[inline]
+            pub fn into_owned_opt(self) -> Option<Box<T>> {
+                self.try_into_owned().ok()
+            }
+        }
+
+        impl<T: ?Sized> $typename<'_, T> {
+            /// Extracts the owned box without cloning.
+            ///
+            /// If the data is owned, the box is moved out. If the data is borrowed,
+            /// the wrapper is returned unchanged as the error.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// let owned: RefOrBox<str> = RefOrBox::Owned(Box::from("owned"));
+            /// assert_eq!(Box::<str>::from("owned"), owned.try_into_box().unwrap());
+            ///
+            /// let borrowed: RefOrBox<str> = RefOrBox::Borrowed("borrowed");
+            /// assert!(borrowed.try_into_box().is_err());
+            /// ```
+            pub fn try_into_box(self) -> Result<Box<T>, Self> {
+                match self {
+                    Self::Owned(owned_value) => Ok(owned_value),
+                    borrowed => Err(borrowed)
+                }
+            }
+        }
+
+        impl<T: ?Sized + ToOwned> $typename<'_, T> where T::Owned: From<Box<T>> {
+            /// Obtains the owned counterpart of T, as given by `ToOwned`, such as `String`
+            /// for `str` and `Vec<T>` for `[T]`.
+            ///
+            /// If the data is borrowed, it is converted with `to_owned`. If the data is owned,
+            /// the box is converted without copying.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// let borrowed: RefOrBox<str> = RefOrBox::Borrowed("borrowed");
+            /// let owned: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([1, 2]));
+            ///
+            /// assert_eq!(String::from("borrowed"), borrowed.into_owned_value());
+            /// assert_eq!(vec![1, 2], owned.into_owned_value());
+            /// ```
+            pub fn into_owned_value(self) -> T::Owned {
+                match self {
+                    Self::Borrowed(borrowed_value) => borrowed_value.to_owned(),
+                    Self::Owned(owned_box) => T::Owned::from(owned_box)
+                }
+            }
+        }
+
         #[cfg(feature = "trait-clone")]
         impl<T: ?Sized> $typename<'_, T> where T: dyn_clone::DynClone {
             /// Obtains an owned value of T. This requires the "trait-clone"
@@ -172,6 +550,63 @@ macro_rules! ref_or_box_impls {
                    Self::Owned(owned_value) => owned_value
                }
             }
+
+            /// Creates a detached owned duplicate, leaving this wrapper untouched. This
+            /// requires the "trait-clone" feature and relies on the dyn-clone crate.
+            pub fn cloned(&self) -> Self {
+                Self::Owned(dyn_clone::clone_box(self.deref()))
+            }
+        }
+
+        #[cfg(feature = "trait-clone")]
+        impl<T: ?Sized + 'static> $typename<'_, T> where T: dyn_clone::DynClone {
+            /// Erases the lifetime, by cloning a borrowed value into a box or moving an
+            /// owned box. This requires the "trait-clone" feature and relies on the
+            /// dyn-clone crate.
+            pub fn into_static(self) -> $typename<'static, T> {
+                $typename::Owned(self.into_owned())
+            }
+        }
+
+        impl<T: ?Sized + crate::dyn_cmp::AsAny> $typename<'_, T> {
+            /// Determines whether both wrapped values have the same concrete type.
+            ///
+            /// For trait objects, the trait must have `AsAny` as a supertrait. The type ID
+            /// is then looked up through the vtable, so this compares the implementing types,
+            /// regardless of whether either side is borrowed or owned.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// use polymorph::dyn_cmp::AsAny;
+            ///
+            /// trait Shape: AsAny {}
+            /// struct Circle;
+            /// struct Square;
+            /// impl Shape for Circle {}
+            /// impl Shape for Square {}
+            ///
+            /// let circle: RefOrBox<dyn Shape> = RefOrBox::Borrowed(&Circle);
+            /// let other_circle: RefOrBox<dyn Shape> = RefOrBox::Owned(Box::new(Circle));
+            /// let square: RefOrBox<dyn Shape> = RefOrBox::Owned(Box::new(Square));
+            ///
+            /// assert!(circle.same_impl(&other_circle));
+            /// assert!(!circle.same_impl(&square));
+            /// ```
+            ///
+            /// Trait objects whose trait lacks the supertrait are rejected, since their
+            /// type ID would be that of the trait object type itself:
+            ///
+            /// ```rust,compile_fail
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// use std::fmt::Display;
+            ///
+            /// let number: RefOrBox<dyn Display> = RefOrBox::Owned(Box::new(1_u8));
+            /// let text: RefOrBox<dyn Display> = RefOrBox::Borrowed(&"text");
+            /// number.same_impl(&text);
+            /// ```
+            pub fn same_impl<U: ?Sized + crate::dyn_cmp::AsAny>(&self, other: &$typename<'_, U>) -> bool {
+                self.deref().as_any().type_id() == other.deref().as_any().type_id()
+            }
         }
 
         impl<T: ?Sized> AsRef<T> for $typename<'_, T> {
@@ -188,18 +623,21 @@ macro_rules! ref_or_box_impls {
             }
         }
 
+        #[allow(clippy::partialeq_ne_impl)]
         impl<T: ?Sized + PartialEq<U>, U: ?Sized> PartialEq<$typename<'_, U>> for $typename<'_, T> {
             #[inline]
             fn eq(&self, other: &$typename<'_, U>) -> bool {
-               self.deref().eq(other.deref())
+               deref_eq(self.deref(), other.deref())
             }
 
             #[inline]
             fn ne(&self, other: &$typename<'_, U>) -> bool {
-                self.deref().ne(other.deref())
+               deref_ne(self.deref(), other.deref())
             }
         }
 
+        impl<T: ?Sized + Eq> Eq for $typename<'_, T> {}
+
         impl<T: ?Sized + PartialOrd<U>, U: ?Sized> PartialOrd<$typename<'_, U>> for $typename<'_, T> {
             #[inline]
             fn partial_cmp(&self, other: &$typename<'_, U>) -> Option<Ordering> {
@@ -207,13 +645,455 @@ macro_rules! ref_or_box_impls {
             }
         }
 
+        impl<T: ?Sized + Hash> Hash for $typename<'_, T> {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.deref().hash(state)
+            }
+        }
+
+        impl<T: ?Sized + Ord> Ord for $typename<'_, T> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.deref().cmp(other.deref())
+            }
+        }
+
         impl<T: ?Sized + Display> Display for $typename<'_, T> {
             fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
                 self.deref().fmt(f)
             }
         }
+
+        formatting_impls!(?Sized $typename; LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp);
+
+        impl<T: ?Sized> std::fmt::Pointer for $typename<'_, T> {
+            /// Formats the address of the value: the referent if borrowed, or the box allocation
+            /// if owned. Wrappers viewing the same object format the same address.
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Pointer::fmt(&(self.deref() as *const T).cast::<()>(), f)
+            }
+        }
+
+        impl<T: ?Sized + DebugPayload> std::fmt::Debug for $typename<'_, T> {
+            /// Formats the variant and the value. Trait objects which are not `Debug` may
+            /// be formatted by their type name instead, using `impl_opaque_debug!`.
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Borrowed(borrowed_value) => debug_variant(f, "Borrowed", &Payload(&**borrowed_value)),
+                    Self::Owned(owned_value) => debug_variant(f, "Owned", &Payload(owned_value.deref()))
+                }
+            }
+        }
+    }
+}
+
+macro_rules! shared_or_owned_impls {
+    ($typename:ident, $pointer:ident) => {
+        impl<T> From<$pointer<T>> for $typename<T> {
+            fn from(value: $pointer<T>) -> Self {
+                Self::Shared(value)
+            }
+        }
+
+        impl<T> From<T> for $typename<T> {
+            fn from(value: T) -> Self {
+                Self::Owned(value)
+            }
+        }
+
+        impl<T: Default> Default for $typename<T> {
+            fn default() -> Self {
+                Self::Owned(T::default())
+            }
+        }
+
+        impl<T> Deref for $typename<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                match self {
+                    Self::Shared(shared_value) => shared_value,
+                    Self::Owned(owned_value) => owned_value
+                }
+            }
+        }
+
+        impl<T: Clone> DerefMut for $typename<T> {
+            /// Obtains a mutable reference to the value.
+            ///
+            /// If the value is shared with other pointers, it is first cloned,
+            /// so that this pointer uniquely owns it.
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                match self {
+                    Self::Shared(shared_value) => $pointer::make_mut(shared_value),
+                    Self::Owned(owned_value) => owned_value
+                }
+            }
+        }
+
+        impl<T> $typename<T> {
+            /// Wraps a shared pointer, taking the value out of it if the pointer is unique.
+            ///
+            /// If there are no other strong references to the value, the Owned variant is
+            /// returned without cloning. Otherwise, the shared pointer is kept as is.
+            pub fn from_shared(value: $pointer<T>) -> Self {
+                match $pointer::try_unwrap(value) {
+                    Ok(owned_value) => Self::Owned(owned_value),
+                    Err(shared_value) => Self::Shared(shared_value)
+                }
+            }
+        }
+
+        impl<T> $typename<T> where T: Clone {
+            /// Obtains an owned value of T.
+            ///
+            /// If the data is shared and there are no other strong references to it,
+            /// the value will be moved out of the shared pointer. If there are other
+            /// strong references, it will be cloned.
+            /// If the data is owned, the owned value will be moved out.
+            pub fn into_owned(self) -> T {
+                match self {
+                    Self::Shared(shared_value) => $pointer::try_unwrap(shared_value).unwrap_or_else(|shared_value| (*shared_value).clone()),
+                    Self::Owned(owned_value) => owned_value
+                }
+            }
+        }
+
+        impl<T: Clone> Clone for $typename<T> {
+            fn clone(&self) -> Self {
+                match self {
+                    Self::Shared(shared_value) => Self::Shared($pointer::clone(shared_value)),
+                    Self::Owned(owned_value) => Self::Owned(owned_value.clone())
+                }
+            }
+        }
+
+        impl<T> AsRef<T> for $typename<T> {
+            #[inline]
+            fn as_ref(&self) -> &T {
+                self.deref()
+            }
+        }
+
+        impl<T> Borrow<T> for $typename<T> {
+            #[inline]
+            fn borrow(&self) -> &T {
+                self.deref()
+            }
+        }
+
+        impl<T: PartialEq<U>, U> PartialEq<$typename<U>> for $typename<T> {
+            #[inline]
+            fn eq(&self, other: &$typename<U>) -> bool {
+               deref_eq(self.deref(), other.deref())
+            }
+        }
+
+        impl<T: Eq> Eq for $typename<T> {}
+
+        impl<T: PartialOrd<U>, U> PartialOrd<$typename<U>> for $typename<T> {
+            #[inline]
+            fn partial_cmp(&self, other: &$typename<U>) -> Option<Ordering> {
+                self.deref().partial_cmp(other.deref())
+            }
+        }
+
+        impl<T: Ord> Ord for $typename<T> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.deref().cmp(other.deref())
+            }
+        }
+
+        impl<T: Hash> Hash for $typename<T> {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.deref().hash(state)
+            }
+        }
+
+        impl<T: Display> Display for $typename<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                self.deref().fmt(f)
+            }
+        }
+
+        formatting_impls!(shared $typename; LowerHex, UpperHex, Octal, Binary, LowerExp, UpperExp);
+
+        impl<T: std::fmt::Debug> std::fmt::Debug for $typename<T> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Shared(shared_value) => debug_variant(f, "Shared", shared_value),
+                    Self::Owned(owned_value) => debug_variant(f, "Owned", owned_value)
+                }
+            }
+        }
+
+        impl<T: Clone> From<$typename<T>> for RefOrOwned<'_, T> {
+            /// Converts to the Owned variant.
+            ///
+            /// The value is moved if it is owned or if the shared pointer is unique.
+            /// Otherwise, it is cloned.
+            fn from(value: $typename<T>) -> Self {
+                Self::Owned(value.into_owned())
+            }
+        }
+
+        impl<T: Clone> From<RefOrOwned<'_, T>> for $typename<T> {
+            /// Converts to the Owned variant.
+            ///
+            /// An owned value is moved, and a borrowed value is cloned.
+            fn from(value: RefOrOwned<'_, T>) -> Self {
+                Self::Owned(value.into_owned())
+            }
+        }
+
+        impl<T: Clone> From<$typename<T>> for RefOrBox<'_, T> {
+            /// Converts to the Owned variant.
+            ///
+            /// The value is moved if it is owned or if the shared pointer is unique.
+            /// Otherwise, it is cloned. In either case, a new box is allocated.
+            fn from(value: $typename<T>) -> Self {
+                Self::Owned(Box::new(value.into_owned()))
+            }
+        }
+
+        impl<T: Clone> From<RefOrBox<'_, T>> for $typename<T> {
+            /// Converts to the Owned variant.
+            ///
+            /// An owned value is moved out of its box, and a borrowed value is cloned.
+            fn from(value: RefOrBox<'_, T>) -> Self {
+                match value {
+                    RefOrBox::Borrowed(borrowed_value) => Self::Owned(borrowed_value.clone()),
+                    RefOrBox::Owned(owned_value) => Self::Owned(*owned_value)
+                }
+            }
+        }
+    }
+}
+
+macro_rules! error_downcast_impls {
+    ($error:ty; $($bounds:tt)+) => {
+        impl<'t> RefOrBox<'t, $error> {
+            /// Boxes a concrete error, as the Owned variant.
+            ///
+            /// A `From<E>` implementation would conflict with `From<&T>`, so this function
+            /// takes its place. It can be passed to `map_err` before applying `?`.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::RefOrBox;
+            /// use std::error::Error;
+            ///
+            /// type DynError = dyn Error + Send + Sync;
+            ///
+            /// fn parse(text: &str) -> Result<u32, RefOrBox<'static, DynError>> {
+            ///     let number = text.parse().map_err(RefOrBox::<DynError>::from_error)?;
+            ///     Ok(number)
+            /// }
+            ///
+            /// assert!(parse("x").unwrap_err().is_owned());
+            /// ```
+            pub fn from_error<E: $($bounds)+ + 'static>(error: E) -> Self {
+                Self::Owned(Box::new(error))
+            }
+
+            /// Obtains a reference to the error if it has the concrete type `E`.
+            pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+                (**self).downcast_ref()
+            }
+
+            /// Attempts to convert the error to the concrete type `E`, keeping the
+            /// variant. If the error has another type, the wrapper is returned unchanged.
+            ///
+            /// ```rust
+            /// # use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+            /// use std::error::Error;
+            /// use std::fmt;
+            ///
+            /// #[derive(Debug)]
+            /// struct Timeout;
+            /// impl fmt::Display for Timeout {
+            ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            ///         f.write_str("timed out")
+            ///     }
+            /// }
+            /// impl Error for Timeout {}
+            ///
+            /// let error: RefOrBox<dyn Error + Send + Sync> = RefOrBox::Owned(Box::new(Timeout));
+            /// assert!(matches!(error.downcast::<Timeout>(), Ok(RefOrOwned::Owned(Timeout))));
+            /// ```
+            pub fn downcast<E: Error + 'static>(self) -> Result<RefOrOwned<'t, E>, Self> {
+                match self {
+                    Self::Borrowed(borrowed_value) => match borrowed_value.downcast_ref() {
+                        Some(downcasted) => Ok(RefOrOwned::Borrowed(downcasted)),
+                        None => Err(Self::Borrowed(borrowed_value))
+                    },
+                    Self::Owned(owned_value) => match owned_value.downcast() {
+                        Ok(downcasted) => Ok(RefOrOwned::Owned(*downcasted)),
+                        Err(owned_value) => Err(Self::Owned(owned_value))
+                    }
+                }
+            }
+
+            /// Iterates over the error and its chain of sources, starting with the error itself.
+            pub fn chain(&self) -> ErrorChain<'_> {
+                ErrorChain::new(&**self)
+            }
+
+            /// The last error in the chain of sources, which is the error itself if it has no source.
+            pub fn root_cause(&self) -> &(dyn Error + 'static) {
+                self.chain().last().unwrap_or(&**self)
+            }
+        }
+    }
+}
+
+macro_rules! cross_comparison_impls {
+    ($lhs:ident<T $(: ?$lhs_sized:ident)?>, $rhs:ident<U $(: ?$rhs_sized:ident)?>) => {
+        impl<T: PartialEq<U> $(+ ?$lhs_sized)?, U $(: ?$rhs_sized)?> PartialEq<$rhs<'_, U>> for $lhs<'_, T> {
+            #[inline]
+            fn eq(&self, other: &$rhs<'_, U>) -> bool {
+                deref_eq(self.deref(), other.deref())
+            }
+        }
+
+        impl<T: PartialOrd<U> $(+ ?$lhs_sized)?, U $(: ?$rhs_sized)?> PartialOrd<$rhs<'_, U>> for $lhs<'_, T> {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs<'_, U>) -> Option<Ordering> {
+                self.deref().partial_cmp(other.deref())
+            }
+        }
+    }
+}
+
+macro_rules! kind_impls {
+    ($typename:ident<T $(: ?$sized:ident)?>) => {
+        impl<T $(: ?$sized)?> $typename<'_, T> {
+            /// Determines which variant is held, without destructuring the wrapper.
+            pub fn kind(&self) -> Kind {
+                match self {
+                    Self::Borrowed(_) => Kind::Borrowed,
+                    Self::Owned(_) => Kind::Owned
+                }
+            }
+
+            /// Whether the value is borrowed
+            #[inline]
+            pub fn is_borrowed(&self) -> bool {
+                self.kind() == Kind::Borrowed
+            }
+
+            /// Whether the value is owned
+            #[inline]
+            pub fn is_owned(&self) -> bool {
+                self.kind() == Kind::Owned
+            }
+        }
+    }
+}
+
+macro_rules! iterator_impls {
+    ($typename:ident<I $(: ?$sized:ident)?>) => {
+        impl<I: Iterator $(+ ?$sized)?> Iterator for $typename<'_, I> {
+            type Item = I::Item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.deref_mut().next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.deref().size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.deref_mut().nth(n)
+            }
+        }
+
+        impl<I: DoubleEndedIterator $(+ ?$sized)?> DoubleEndedIterator for $typename<'_, I> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.deref_mut().next_back()
+            }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.deref_mut().nth_back(n)
+            }
+        }
+
+        impl<I: ExactSizeIterator $(+ ?$sized)?> ExactSizeIterator for $typename<'_, I> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.deref().len()
+            }
+        }
+
+        impl<I: std::iter::FusedIterator $(+ ?$sized)?> std::iter::FusedIterator for $typename<'_, I> {}
+    }
+}
+
+macro_rules! extend_impls {
+    ($typename:ident<T $(: ?$sized:ident)?>) => {
+        impl<A, T: Extend<A> $(+ ?$sized)?> Extend<A> for $typename<'_, T> {
+            #[inline]
+            fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+                self.deref_mut().extend(iter)
+            }
+        }
+    }
+}
+
+macro_rules! reference_comparison_impls {
+    ($typename:ident<T $(: ?$sized:ident)?>) => {
+        impl<T: PartialEq $(+ ?$sized)?> PartialEq<&T> for $typename<'_, T> {
+            #[inline]
+            fn eq(&self, other: &&T) -> bool {
+                deref_eq(self.deref(), *other)
+            }
+        }
+
+        impl<T: PartialOrd $(+ ?$sized)?> PartialOrd<&T> for $typename<'_, T> {
+            #[inline]
+            fn partial_cmp(&self, other: &&T) -> Option<Ordering> {
+                self.deref().partial_cmp(*other)
+            }
+        }
+    }
+}
+
+macro_rules! value_comparison_impls {
+    ($typename:ident; $($value:ty),*) => {
+        $(
+        impl PartialEq<$value> for $typename<'_, $value> {
+            #[inline]
+            fn eq(&self, other: &$value) -> bool {
+                deref_eq(self.deref(), other)
+            }
+        }
+
+        impl PartialOrd<$value> for $typename<'_, $value> {
+            #[inline]
+            fn partial_cmp(&self, other: &$value) -> Option<Ordering> {
+                self.deref().partial_cmp(other)
+            }
+        }
+        )*
     }
 }
 
 pub(crate) use ref_or_owned_impls;
 pub(crate) use ref_or_box_impls;
+pub(crate) use shared_or_owned_impls;
+pub(crate) use formatting_impls;
+pub(crate) use error_downcast_impls;
+pub(crate) use cross_comparison_impls;
+pub(crate) use kind_impls;
+pub(crate) use iterator_impls;
+pub(crate) use extend_impls;
+pub(crate) use reference_comparison_impls;
+pub(crate) use value_comparison_impls;
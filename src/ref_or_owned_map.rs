@@ -0,0 +1,155 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefMutOrOwned, RefOrBox, RefMutOrBox};
+
+impl<'t, T: 't> RefOrOwned<'t, T> {
+    /// Transforms the wrapped value, preserving whether it is borrowed or owned.
+    ///
+    /// The first function projects a borrowed value, and the second transforms an
+    /// owned one. This is commonly used to narrow a wrapper down to one of the fields
+    /// of the wrapped value, without cloning.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// struct User {
+    ///     name: String,
+    ///     age: u8
+    /// }
+    ///
+    /// fn name_of(user: RefOrOwned<'_, User>) -> RefOrOwned<'_, String> {
+    ///     user.map_variant(|user| &user.name, |user| user.name)
+    /// }
+    ///
+    /// let user = User { name: String::from("Alice"), age: 30 };
+    /// assert!(matches!(name_of(RefOrOwned::Borrowed(&user)), RefOrOwned::Borrowed(_)));
+    /// assert_eq!("Alice", *name_of(RefOrOwned::Owned(user)));
+    /// ```
+    pub fn map_variant<U, R, O>(self, map_ref: R, map_owned: O) -> RefOrOwned<'t, U>
+        where R: FnOnce(&'t T) -> &'t U, O: FnOnce(T) -> U {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrOwned::Borrowed(map_ref(borrowed_value)),
+            Self::Owned(owned_value) => RefOrOwned::Owned(map_owned(owned_value))
+        }
+    }
+
+    /// Transforms the wrapped value with fallible functions, preserving whether it
+    /// is borrowed or owned. The error of whichever function is called is returned.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// struct Config {
+    ///     port: Option<u16>
+    /// }
+    ///
+    /// fn port_of(config: RefOrOwned<'_, Config>) -> Result<RefOrOwned<'_, u16>, &'static str> {
+    ///     config.try_map_variant(
+    ///         |config| config.port.as_ref().ok_or("No port"),
+    ///         |config| config.port.ok_or("No port"))
+    /// }
+    ///
+    /// let config = Config { port: Some(8080) };
+    /// assert_eq!(8080, *port_of(RefOrOwned::Borrowed(&config)).unwrap());
+    /// assert!(port_of(RefOrOwned::Owned(Config { port: None })).is_err());
+    /// ```
+    pub fn try_map_variant<U, E, R, O>(self, map_ref: R, map_owned: O) -> Result<RefOrOwned<'t, U>, E>
+        where R: FnOnce(&'t T) -> Result<&'t U, E>, O: FnOnce(T) -> Result<U, E> {
+
+        Ok(match self {
+            Self::Borrowed(borrowed_value) => RefOrOwned::Borrowed(map_ref(borrowed_value)?),
+            Self::Owned(owned_value) => RefOrOwned::Owned(map_owned(owned_value)?)
+        })
+    }
+}
+
+impl<'t, T: 't> RefMutOrOwned<'t, T> {
+    /// Transforms the wrapped value, preserving whether it is borrowed or owned.
+    ///
+    /// The first function projects a borrowed value, and the second transforms an
+    /// owned one.
+    pub fn map_variant<U, R, O>(self, map_ref: R, map_owned: O) -> RefMutOrOwned<'t, U>
+        where R: FnOnce(&'t mut T) -> &'t mut U, O: FnOnce(T) -> U {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefMutOrOwned::Borrowed(map_ref(borrowed_value)),
+            Self::Owned(owned_value) => RefMutOrOwned::Owned(map_owned(owned_value))
+        }
+    }
+
+    /// Transforms the wrapped value with fallible functions, preserving whether it
+    /// is borrowed or owned. The error of whichever function is called is returned.
+    pub fn try_map_variant<U, E, R, O>(self, map_ref: R, map_owned: O) -> Result<RefMutOrOwned<'t, U>, E>
+        where R: FnOnce(&'t mut T) -> Result<&'t mut U, E>, O: FnOnce(T) -> Result<U, E> {
+
+        Ok(match self {
+            Self::Borrowed(borrowed_value) => RefMutOrOwned::Borrowed(map_ref(borrowed_value)?),
+            Self::Owned(owned_value) => RefMutOrOwned::Owned(map_owned(owned_value)?)
+        })
+    }
+}
+
+impl<'t, T: ?Sized + 't> RefOrBox<'t, T> {
+    /// Transforms the wrapped value, preserving whether it is borrowed or owned.
+    ///
+    /// The first function projects a borrowed value, and the second transforms an
+    /// owned box.
+    pub fn map_variant<U, R, O>(self, map_ref: R, map_owned: O) -> RefOrBox<'t, U>
+        where U: ?Sized + 't, R: FnOnce(&'t T) -> &'t U, O: FnOnce(Box<T>) -> Box<U> {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefOrBox::Borrowed(map_ref(borrowed_value)),
+            Self::Owned(owned_box) => RefOrBox::Owned(map_owned(owned_box))
+        }
+    }
+
+    /// Transforms the wrapped value with fallible functions, preserving whether it
+    /// is borrowed or owned. The error of whichever function is called is returned.
+    pub fn try_map_variant<U, E, R, O>(self, map_ref: R, map_owned: O) -> Result<RefOrBox<'t, U>, E>
+        where U: ?Sized + 't, R: FnOnce(&'t T) -> Result<&'t U, E>, O: FnOnce(Box<T>) -> Result<Box<U>, E> {
+
+        Ok(match self {
+            Self::Borrowed(borrowed_value) => RefOrBox::Borrowed(map_ref(borrowed_value)?),
+            Self::Owned(owned_box) => RefOrBox::Owned(map_owned(owned_box)?)
+        })
+    }
+}
+
+impl<'t, T: ?Sized + 't> RefMutOrBox<'t, T> {
+    /// Transforms the wrapped value, preserving whether it is borrowed or owned.
+    ///
+    /// The first function projects a borrowed value, and the second transforms an
+    /// owned box.
+    pub fn map_variant<U, R, O>(self, map_ref: R, map_owned: O) -> RefMutOrBox<'t, U>
+        where U: ?Sized + 't, R: FnOnce(&'t mut T) -> &'t mut U, O: FnOnce(Box<T>) -> Box<U> {
+
+        match self {
+            Self::Borrowed(borrowed_value) => RefMutOrBox::Borrowed(map_ref(borrowed_value)),
+            Self::Owned(owned_box) => RefMutOrBox::Owned(map_owned(owned_box))
+        }
+    }
+
+    /// Transforms the wrapped value with fallible functions, preserving whether it
+    /// is borrowed or owned. The error of whichever function is called is returned.
+    pub fn try_map_variant<U, E, R, O>(self, map_ref: R, map_owned: O) -> Result<RefMutOrBox<'t, U>, E>
+        where U: ?Sized + 't, R: FnOnce(&'t mut T) -> Result<&'t mut U, E>, O: FnOnce(Box<T>) -> Result<Box<U>, E> {
+
+        Ok(match self {
+            Self::Borrowed(borrowed_value) => RefMutOrBox::Borrowed(map_ref(borrowed_value)?),
+            Self::Owned(owned_box) => RefMutOrBox::Owned(map_owned(owned_box)?)
+        })
+    }
+}
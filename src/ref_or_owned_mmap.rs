@@ -0,0 +1,154 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Deref;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use memmap2::Mmap;
+
+/// A type which can be either a memory-mapped region, or an owned byte vector.
+/// This requires the "memmap2" feature.
+///
+/// The Mapped variant plays the role of a borrowed value: its bytes live in the
+/// mapped file rather than on the heap, so large files may be processed without
+/// being copied. When the data needs to be modified or retained independently of
+/// the file, it may be promoted to the Owned variant.
+///
+/// Creating a file-backed `Mmap` is unsafe, and is therefore left to the caller.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::MmapOrVec;
+/// fn checksum(data: &MmapOrVec) -> u64 {
+///     data.iter().map(|&byte| u64::from(byte)).sum()
+/// }
+///
+/// let data = MmapOrVec::from(vec![1, 2, 3]);
+/// assert_eq!(6, checksum(&data));
+/// ```
+#[derive(Debug)]
+pub enum MmapOrVec {
+    Mapped(Mmap),
+    Owned(Vec<u8>)
+}
+
+impl From<Mmap> for MmapOrVec {
+    fn from(value: Mmap) -> Self {
+        Self::Mapped(value)
+    }
+}
+
+impl From<Vec<u8>> for MmapOrVec {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl Default for MmapOrVec {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl Deref for MmapOrVec {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Mapped(mapped_value) => mapped_value,
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl MmapOrVec {
+    /// Obtains an owned byte vector.
+    ///
+    /// If the data is mapped, it will be copied out of the mapped region,
+    /// and the mapping will be released.
+    /// If the data is owned, the owned value will be moved out.
+    pub fn into_owned(self) -> Vec<u8> {
+        match self {
+            Self::Mapped(mapped_value) => mapped_value.to_vec(),
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+
+    /// Obtains a mutable reference to the owned byte vector.
+    ///
+    /// If the data is mapped, it will first be copied into the Owned variant,
+    /// and the mapping will be released.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::MmapOrVec;
+    /// let mut data = MmapOrVec::default();
+    /// data.to_mut().push(7);
+    /// assert_eq!(&[7], &*data);
+    /// ```
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        if let Self::Mapped(mapped_value) = self {
+            *self = Self::Owned(mapped_value.to_vec());
+        }
+        match self {
+            Self::Mapped(_) => unreachable!(),
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl AsRef<[u8]> for MmapOrVec {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl Borrow<[u8]> for MmapOrVec {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl PartialEq for MmapOrVec {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.deref().eq(other.deref())
+    }
+}
+
+impl Eq for MmapOrVec {}
+
+impl PartialOrd for MmapOrVec {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MmapOrVec {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl Hash for MmapOrVec {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Deref;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A type which can be either a pinned mutable reference, or an owned pinned box.
+///
+/// Unlike `RefMutOrBox`, this does not require `T: Unpin`. Values which are already
+/// pinned elsewhere, such as futures and other self-referential state machines, can be
+/// wrapped as the Borrowed variant, while the Owned variant pins its own box.
+///
+/// If `T` is a future, `PinRefMutOrBox` is itself a future which polls the wrapped value.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::PinRefMutOrBox;
+/// use std::cell::Cell;
+/// use std::marker::PhantomPinned;
+/// use std::pin::Pin;
+///
+/// struct Counter {
+///     count: Cell<u32>,
+///     _pinned: PhantomPinned
+/// }
+///
+/// impl Counter {
+///     fn new() -> Self {
+///         Self { count: Cell::new(0), _pinned: PhantomPinned }
+///     }
+///
+///     fn advance(self: Pin<&mut Self>) -> u32 {
+///         self.count.set(self.count.get() + 1);
+///         self.count.get()
+///     }
+/// }
+///
+/// fn advance_twice(mut counter: PinRefMutOrBox<'_, Counter>) -> u32 {
+///     counter.as_mut().advance();
+///     counter.as_mut().advance()
+/// }
+///
+/// let mut pinned_elsewhere = Box::pin(Counter::new());
+/// assert_eq!(2, advance_twice(PinRefMutOrBox::Borrowed(pinned_elsewhere.as_mut())));
+/// assert_eq!(4, advance_twice(PinRefMutOrBox::Borrowed(pinned_elsewhere.as_mut())));
+/// assert_eq!(2, advance_twice(PinRefMutOrBox::from(Box::new(Counter::new()))));
+/// ```
+#[derive(Debug)]
+pub enum PinRefMutOrBox<'t, T: ?Sized + 't> {
+    Borrowed(Pin<&'t mut T>),
+    Owned(Pin<Box<T>>)
+}
+
+impl<'t, T: ?Sized> PinRefMutOrBox<'t, T> {
+    /// Obtains a pinned mutable reference to the value.
+    pub fn as_mut(&mut self) -> Pin<&mut T> {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.as_mut(),
+            Self::Owned(owned_value) => owned_value.as_mut()
+        }
+    }
+}
+
+impl<'t, T: ?Sized> From<Pin<&'t mut T>> for PinRefMutOrBox<'t, T> {
+    fn from(value: Pin<&'t mut T>) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<T: ?Sized> From<Pin<Box<T>>> for PinRefMutOrBox<'_, T> {
+    fn from(value: Pin<Box<T>>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for PinRefMutOrBox<'_, T> {
+    fn from(value: Box<T>) -> Self {
+        Self::Owned(Box::into_pin(value))
+    }
+}
+
+impl<T: ?Sized> Deref for PinRefMutOrBox<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value,
+            Self::Owned(owned_value) => owned_value
+        }
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for PinRefMutOrBox<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T: ?Sized + Future> Future for PinRefMutOrBox<'_, T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().as_mut().poll(cx)
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Proptest support for the wrappers. This requires the "proptest" feature.
+//!
+//! Generated values cannot borrow from the strategy or the value tree, so each generated
+//! value is an arena of its own: it owns the payload, and lends it out as a wrapper of
+//! the chosen variant for the duration of a closure.
+
+use std::fmt::Debug;
+use proptest::prelude::*;
+use super::{Kind, RefMutOrBox, RefMutOrOwned, RefOrBox, RefOrOwned};
+
+/// A generated payload, together with the variant of the wrapper to lend it out as.
+///
+/// Borrowed wrappers borrow from the payload held here. Owned wrappers take the payload.
+#[derive(Debug, Clone)]
+pub struct GeneratedWrapper<B> {
+    kind: Kind,
+    payload: B
+}
+
+impl<B> GeneratedWrapper<B> {
+    /// The variant of wrapper which will be lent out
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Gets the generated payload
+    pub fn payload(&self) -> &B {
+        &self.payload
+    }
+}
+
+impl<T> GeneratedWrapper<T> {
+    /// Lends the payload out as a `RefOrOwned` of the generated variant
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::{ref_or_owned_strategy, RefOrOwned};
+    /// use proptest::prelude::*;
+    /// use proptest::test_runner::TestRunner;
+    ///
+    /// fn length(value: RefOrOwned<'_, String>) -> usize {
+    ///     value.len()
+    /// }
+    ///
+    /// TestRunner::default().run(&ref_or_owned_strategy(".*"), |generated| {
+    ///     let expected = generated.payload().len();
+    ///     prop_assert_eq!(expected, generated.with_ref_or_owned(length));
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    pub fn with_ref_or_owned<R, F>(self, action: F) -> R
+        where F: FnOnce(RefOrOwned<'_, T>) -> R {
+
+        match self.kind {
+            Kind::Borrowed => action(RefOrOwned::Borrowed(&self.payload)),
+            Kind::Owned => action(RefOrOwned::Owned(self.payload))
+        }
+    }
+
+    /// Lends the payload out as a `RefMutOrOwned` of the generated variant
+    pub fn with_ref_mut_or_owned<R, F>(mut self, action: F) -> R
+        where F: FnOnce(RefMutOrOwned<'_, T>) -> R {
+
+        match self.kind {
+            Kind::Borrowed => action(RefMutOrOwned::Borrowed(&mut self.payload)),
+            Kind::Owned => action(RefMutOrOwned::Owned(self.payload))
+        }
+    }
+}
+
+impl<T: ?Sized> GeneratedWrapper<Box<T>> {
+    /// Lends the payload out as a `RefOrBox` of the generated variant
+    pub fn with_ref_or_box<R, F>(self, action: F) -> R
+        where F: FnOnce(RefOrBox<'_, T>) -> R {
+
+        match self.kind {
+            Kind::Borrowed => action(RefOrBox::Borrowed(&self.payload)),
+            Kind::Owned => action(RefOrBox::Owned(self.payload))
+        }
+    }
+
+    /// Lends the payload out as a `RefMutOrBox` of the generated variant
+    pub fn with_ref_mut_or_box<R, F>(mut self, action: F) -> R
+        where F: FnOnce(RefMutOrBox<'_, T>) -> R {
+
+        match self.kind {
+            Kind::Borrowed => action(RefMutOrBox::Borrowed(&mut self.payload)),
+            Kind::Owned => action(RefMutOrBox::Owned(self.payload))
+        }
+    }
+}
+
+fn generated<S: Strategy>(inner: S) -> impl Strategy<Value = GeneratedWrapper<S::Value>> {
+    (any::<bool>(), inner).prop_map(|(borrowed, payload)| GeneratedWrapper {
+        kind: if borrowed { Kind::Borrowed } else { Kind::Owned },
+        payload
+    })
+}
+
+/// Generates the values of the inner strategy, to be lent out as `RefOrOwned` or
+/// `RefMutOrOwned`. Both variants are generated, so that the code paths of consumers
+/// for both are exercised.
+///
+/// Values shrink towards the Owned variant and through to the shrunken inner value.
+pub fn ref_or_owned_strategy<S: Strategy>(inner: S) -> impl Strategy<Value = GeneratedWrapper<S::Value>> {
+    generated(inner)
+}
+
+/// Generates the boxed values of the inner strategy, to be lent out as `RefOrBox` or
+/// `RefMutOrBox`. Both variants are generated, in the same manner as `ref_or_owned_strategy`.
+///
+/// To generate trait objects, map the inner strategy to boxed trait objects first.
+pub fn ref_or_box_strategy<T, S>(inner: S) -> impl Strategy<Value = GeneratedWrapper<Box<T>>>
+    where T: ?Sized + Debug, S: Strategy<Value = Box<T>> {
+
+    generated(inner)
+}
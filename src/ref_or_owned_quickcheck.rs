@@ -0,0 +1,81 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Quickcheck support for the wrappers. This requires the "quickcheck" feature.
+//!
+//! `RefOrOwned` is always generated as the Owned variant, since a Borrowed value would
+//! need somewhere to borrow from which outlives the test case. `ArcOrOwned` and `RcOrOwned`
+//! are generated as both variants. Values shrink to the Owned variant first, then through
+//! to the wrapped value.
+//!
+//! Quickcheck requires `Clone`, which the mutable wrappers do not implement, and which
+//! `RefOrBox` only implements with the "trait-clone" feature. Hence only `RefOrOwned`,
+//! `ArcOrOwned`, and `RcOrOwned` are supported.
+
+use super::{ArcOrOwned, RcOrOwned, RefOrOwned};
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+use quickcheck::{Arbitrary, Gen};
+
+fn shrink_to_owned<T: Arbitrary, W: From<T> + 'static>(value: &T, owned: bool) -> Box<dyn Iterator<Item = W>> {
+    let shrunk = value.shrink().map(W::from);
+    if owned {
+        Box::new(shrunk)
+    } else {
+        Box::new(std::iter::once(W::from(value.clone())).chain(shrunk))
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for RefOrOwned<'static, T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::Owned(T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        shrink_to_owned(self.deref(), self.is_owned())
+    }
+}
+
+impl<T: Arbitrary + Send + Sync> Arbitrary for ArcOrOwned<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let value = T::arbitrary(g);
+        if bool::arbitrary(g) {
+            Self::Shared(Arc::new(value))
+        } else {
+            Self::Owned(value)
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        shrink_to_owned(self.deref(), matches!(self, Self::Owned(_)))
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for RcOrOwned<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let value = T::arbitrary(g);
+        if bool::arbitrary(g) {
+            Self::Shared(Rc::new(value))
+        } else {
+            Self::Owned(value)
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        shrink_to_owned(self.deref(), matches!(self, Self::Owned(_)))
+    }
+}
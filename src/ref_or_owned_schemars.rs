@@ -0,0 +1,53 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! JSON Schema support for the wrappers. This requires the "schemars" feature.
+//!
+//! Every wrapper is described by the schema of the value it holds, in line with
+//! the `Serialize` implementations, which serialize the value in place.
+
+use super::{ArcOrOwned, RcOrOwned, RefMutOrBox, RefMutOrOwned, RefOrBox, RefOrOwned};
+use std::borrow::Cow;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+macro_rules! json_schema_forward {
+    ($($impl:tt)+) => {
+        impl $($impl)+ {
+            fn inline_schema() -> bool {
+                T::inline_schema()
+            }
+
+            fn schema_name() -> Cow<'static, str> {
+                T::schema_name()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                T::schema_id()
+            }
+
+            fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                T::json_schema(generator)
+            }
+        }
+    }
+}
+
+json_schema_forward!(<T: JsonSchema> JsonSchema for RefOrOwned<'_, T>);
+json_schema_forward!(<T: JsonSchema> JsonSchema for RefMutOrOwned<'_, T>);
+json_schema_forward!(<T: ?Sized + JsonSchema> JsonSchema for RefOrBox<'_, T>);
+json_schema_forward!(<T: ?Sized + JsonSchema> JsonSchema for RefMutOrBox<'_, T>);
+json_schema_forward!(<T: JsonSchema> JsonSchema for ArcOrOwned<T>);
+json_schema_forward!(<T: JsonSchema> JsonSchema for RcOrOwned<T>);
@@ -0,0 +1,207 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Serde support for the wrappers. This requires the "serde" feature, and the "rc"
+//! feature for the shared wrappers.
+
+use super::{RefMutOrBox, RefMutOrOwned, RefOrBox, RefOrOwned};
+use std::fmt;
+use std::ops::Deref;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+
+impl<T: Serialize> Serialize for RefOrOwned<'_, T> {
+    /// Serializes the value, whether borrowed or owned, exactly as `T` would be serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for RefMutOrOwned<'_, T> {
+    /// Serializes the value, whether borrowed or owned, exactly as `T` would be serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+impl<T: ?Sized + Serialize> Serialize for RefOrBox<'_, T> {
+    /// Serializes the value, whether borrowed or boxed, exactly as `T` would be serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+impl<T: ?Sized + Serialize> Serialize for RefMutOrBox<'_, T> {
+    /// Serializes the value, whether borrowed or boxed, exactly as `T` would be serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+impl<'de: 't, 't> Deserialize<'de> for RefOrBox<'t, str> {
+    /// Borrows the string from the input if the format allows it, and otherwise
+    /// deserializes it as the Owned variant.
+    ///
+    /// Unlike `Cow<str>`, no `#[serde(borrow)]` attribute is needed, although the
+    /// attribute is still required for derived structs with more than one lifetime.
+    /// Strings which must be unescaped, as in JSON, are owned.
+    ///
+    /// Because the result may borrow from the input, this is not `DeserializeOwned`.
+    /// When reading from a stream, deserialize a `Box<str>` and convert it instead.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+struct StrVisitor;
+
+impl<'de> Visitor<'de> for StrVisitor {
+    type Value = RefOrBox<'de, str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E: Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Borrowed(value))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(Box::from(value)))
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(value.into_boxed_str()))
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, value: &'de [u8]) -> Result<Self::Value, E> {
+        match std::str::from_utf8(value) {
+            Ok(value) => Ok(RefOrBox::Borrowed(value)),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(value), &self))
+        }
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        match std::str::from_utf8(value) {
+            Ok(value) => Ok(RefOrBox::Owned(Box::from(value))),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(value), &self))
+        }
+    }
+}
+
+impl<'de: 't, 't> Deserialize<'de> for RefOrBox<'t, [u8]> {
+    /// Borrows the bytes from the input if the format allows it, and otherwise
+    /// deserializes them as the Owned variant.
+    ///
+    /// Formats without a native byte type, such as JSON, yield a sequence of
+    /// numbers, which is collected into the Owned variant.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = RefOrBox<'de, [u8]>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, value: &'de [u8]) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Borrowed(value))
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(Box::from(value)))
+    }
+
+    fn visit_byte_buf<E: Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(value.into_boxed_slice()))
+    }
+
+    fn visit_borrowed_str<E: Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Borrowed(value.as_bytes()))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(Box::from(value.as_bytes())))
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(RefOrBox::Owned(value.into_bytes().into_boxed_slice()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // The length hint comes from the input, so it is capped like serde caps it
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(RefOrBox::Owned(bytes.into_boxed_slice()))
+    }
+}
+
+// As with serde's own implementations for Rc and Arc, these are behind the "rc"
+// feature, since deserializing them does not preserve sharing
+#[cfg(feature = "rc")]
+mod shared {
+    use super::*;
+    use crate::ref_or_owned::{ArcOrOwned, RcOrOwned};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    impl<T: Serialize> Serialize for ArcOrOwned<T> {
+        /// Serializes the value in place, without copying shared data.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.deref().serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for ArcOrOwned<T> {
+        /// Deserializes the value into a new `Arc`, as the Shared variant.
+        ///
+        /// Serde has no notion of object identity, so each occurrence in the input gets an
+        /// allocation of its own, and values which were shared before serialization are
+        /// not shared with each other after deserialization. However, the result is shared
+        /// from the start, so that it can be cloned cheaply. Because
+        /// the new `Arc` is unique, neither `DerefMut` nor `into_owned` clone the value.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(|value| Self::Shared(Arc::new(value)))
+        }
+    }
+
+    impl<T: Serialize> Serialize for RcOrOwned<T> {
+        /// Serializes the value in place, without copying shared data.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.deref().serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for RcOrOwned<T> {
+        /// Deserializes the value into a new `Rc`, as the Shared variant.
+        ///
+        /// Serde has no notion of object identity, so each occurrence in the input gets an
+        /// allocation of its own, and values which were shared before serialization are
+        /// not shared with each other after deserialization. However, the result is shared
+        /// from the start, so that it can be cloned cheaply. Because
+        /// the new `Rc` is unique, neither `DerefMut` nor `into_owned` clone the value.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(|value| Self::Shared(Rc::new(value)))
+        }
+    }
+}
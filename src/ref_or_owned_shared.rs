@@ -0,0 +1,166 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::{Deref, DerefMut};
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::rc::Rc;
+use super::{RefOrOwned, RefOrBox};
+use super::ref_or_owned_macros::*;
+
+/// A type which can be either a shared atomically reference-counted value, or an owned value.
+///
+/// `ArcOrOwned` implements `Deref` for `T`. Where `T: Clone`, it also implements
+/// `DerefMut`, which behaves like `Arc::make_mut`: a shared value is cloned only if
+/// there are other references to it.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::ArcOrOwned;
+/// use std::sync::Arc;
+///
+/// let config = Arc::new(vec![1, 2, 3]);
+/// let mut local: ArcOrOwned<Vec<u8>> = ArcOrOwned::from(Arc::clone(&config));
+/// local.push(4);
+///
+/// assert_eq!(vec![1, 2, 3], *config);
+/// assert_eq!(vec![1, 2, 3, 4], *local);
+/// ```
+pub enum ArcOrOwned<T> {
+    Shared(Arc<T>),
+    Owned(T)
+}
+
+impl<T> ArcOrOwned<T> {
+    /// Wraps an `Arc`, taking the value out of it if the `Arc` is unique.
+    ///
+    /// If there are no other strong references to the value, the Owned variant is
+    /// returned without cloning. Otherwise, the `Arc` is kept as the Shared variant.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::ArcOrOwned;
+    /// use std::sync::Arc;
+    ///
+    /// let unique = Arc::new(String::from("unique"));
+    /// assert!(matches!(ArcOrOwned::from_arc(unique), ArcOrOwned::Owned(_)));
+    ///
+    /// let shared = Arc::new(String::from("shared"));
+    /// let _other = Arc::clone(&shared);
+    /// assert!(matches!(ArcOrOwned::from_arc(shared), ArcOrOwned::Shared(_)));
+    /// ```
+    pub fn from_arc(value: Arc<T>) -> Self {
+        Self::from_shared(value)
+    }
+}
+
+shared_or_owned_impls!(ArcOrOwned, Arc);
+
+/// A type which can be either a shared reference-counted value, or an owned value.
+///
+/// This is the single-threaded counterpart of `ArcOrOwned`. Where `T: Clone`, it
+/// implements `DerefMut`, which behaves like `Rc::make_mut`: a shared value is cloned
+/// only if there are other references to it.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RcOrOwned;
+/// use std::rc::Rc;
+///
+/// let palette = Rc::new(vec!["red", "green"]);
+/// let mut themed: RcOrOwned<Vec<&str>> = RcOrOwned::from(Rc::clone(&palette));
+/// themed.push("blue");
+///
+/// assert_eq!(2, palette.len());
+/// assert_eq!(3, themed.len());
+/// ```
+pub enum RcOrOwned<T> {
+    Shared(Rc<T>),
+    Owned(T)
+}
+
+impl<T> RcOrOwned<T> {
+    /// Wraps an `Rc`, taking the value out of it if the `Rc` is unique.
+    ///
+    /// If there are no other strong references to the value, the Owned variant is
+    /// returned without cloning. Otherwise, the `Rc` is kept as the Shared variant.
+    pub fn from_rc(value: Rc<T>) -> Self {
+        Self::from_shared(value)
+    }
+}
+
+shared_or_owned_impls!(RcOrOwned, Rc);
+
+impl<T: Clone> From<ArcOrOwned<T>> for RcOrOwned<T> {
+    /// Converts to the Owned variant.
+    ///
+    /// The value is moved if it is owned or if the `Arc` is unique.
+    /// Otherwise, it is cloned.
+    fn from(value: ArcOrOwned<T>) -> Self {
+        Self::Owned(value.into_owned())
+    }
+}
+
+impl<T: Clone> From<RcOrOwned<T>> for ArcOrOwned<T> {
+    /// Converts to the Owned variant.
+    ///
+    /// The value is moved if it is owned or if the `Rc` is unique.
+    /// Otherwise, it is cloned.
+    fn from(value: RcOrOwned<T>) -> Self {
+        Self::Owned(value.into_owned())
+    }
+}
+
+impl<'t, T> RefOrOwned<'t, T> {
+    /// Takes the value out of an `Arc` if there are no other strong references to it.
+    ///
+    /// Otherwise, the `Arc` is returned as the error.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// use std::sync::Arc;
+    ///
+    /// let unique = Arc::new(String::from("unique"));
+    /// assert!(RefOrOwned::try_from_arc(unique).is_ok());
+    ///
+    /// let shared = Arc::new(String::from("shared"));
+    /// let _other = Arc::clone(&shared);
+    /// assert!(RefOrOwned::try_from_arc(shared).is_err());
+    /// ```
+    pub fn try_from_arc(value: Arc<T>) -> Result<Self, Arc<T>> {
+        Arc::try_unwrap(value).map(Self::Owned)
+    }
+}
+
+impl<'t, T> RefOrOwned<'t, T> where T: Clone {
+    /// Obtains the value held by an `Arc` as the Owned variant.
+    ///
+    /// If there are no other strong references to the value, it is moved out of the `Arc`.
+    /// Otherwise, it is cloned.
+    pub fn from_arc(value: Arc<T>) -> Self {
+        Self::Owned(Arc::try_unwrap(value).unwrap_or_else(|shared_value| (*shared_value).clone()))
+    }
+}
+
+impl<T> TryFrom<Arc<T>> for RefOrOwned<'_, T> {
+    type Error = Arc<T>;
+
+    #[inline]
+    fn try_from(value: Arc<T>) -> Result<Self, Self::Error> {
+        Self::try_from_arc(value)
+    }
+}
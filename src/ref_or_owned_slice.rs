@@ -0,0 +1,476 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefOrBox, RefMutOrBox};
+use std::collections::TryReserveError;
+use std::io::IoSlice;
+use std::mem;
+use std::slice::SliceIndex;
+
+fn try_clone_slice<T: Clone>(slice: &[T]) -> Result<Box<[T]>, TryReserveError> {
+    let mut vec = Vec::new();
+    vec.try_reserve_exact(slice.len())?;
+    vec.extend_from_slice(slice);
+    Ok(vec.into_boxed_slice())
+}
+
+fn try_clone_str(string: &str) -> Result<Box<str>, TryReserveError> {
+    let mut owned = String::new();
+    owned.try_reserve_exact(string.len())?;
+    owned.push_str(string);
+    Ok(owned.into_boxed_str())
+}
+
+impl<'t, T: Clone> RefOrBox<'t, [T]> {
+    /// Obtains an owned boxed slice, using fallible allocation.
+    ///
+    /// If the data is borrowed, its elements will be cloned into a new allocation.
+    /// If the data is owned, the owned box will be moved out.
+    ///
+    /// Unlike an infallible clone, this reports allocation failure as an error
+    /// rather than aborting the process.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let borrowed: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 2, 3]);
+    /// assert_eq!(&[1, 2, 3], &*borrowed.try_into_owned_fallible().unwrap());
+    /// ```
+    pub fn try_into_owned_fallible(self) -> Result<Box<[T]>, TryReserveError> {
+        match self {
+            Self::Borrowed(borrowed_value) => try_clone_slice(borrowed_value),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+
+    /// Obtains a mutable reference to the owned slice, using fallible allocation.
+    ///
+    /// If the data is borrowed, it will first be cloned into the Owned variant.
+    /// If allocation fails, the error is returned and the data stays borrowed.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let data = [1, 2, 3];
+    /// let mut slice: RefOrBox<[u8]> = RefOrBox::Borrowed(&data);
+    /// slice.try_to_mut().unwrap()[0] = 7;
+    /// assert_eq!(&[7, 2, 3], &*slice);
+    /// ```
+    pub fn try_to_mut(&mut self) -> Result<&mut [T], TryReserveError> {
+        if let Self::Borrowed(borrowed_value) = self {
+            *self = Self::Owned(try_clone_slice(borrowed_value)?);
+        }
+        match self {
+            Self::Borrowed(_) => unreachable!(),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+
+    /// Sorts the slice, cloning it only if it is borrowed and not already sorted.
+    ///
+    /// An owned slice is sorted in place. A borrowed slice which is already
+    /// sorted is returned unchanged, without allocating.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let sorted: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 2, 3]);
+    /// assert!(matches!(sorted.sorted(), RefOrBox::Borrowed(_)));
+    ///
+    /// let unsorted: RefOrBox<[u8]> = RefOrBox::Borrowed(&[3, 1, 2]);
+    /// assert_eq!(&[1, 2, 3], &*unsorted.sorted());
+    /// ```
+    pub fn sorted(self) -> Self where T: Ord {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                if borrowed_value.windows(2).all(|pair| pair[0] <= pair[1]) {
+                    return Self::Borrowed(borrowed_value);
+                }
+                let mut owned_value = borrowed_value.to_vec();
+                owned_value.sort();
+                Self::Owned(owned_value.into_boxed_slice())
+            },
+            Self::Owned(mut owned_value) => {
+                owned_value.sort();
+                Self::Owned(owned_value)
+            }
+        }
+    }
+
+    /// Removes consecutive repeated elements, cloning the slice only if it is borrowed
+    /// and contains such repetitions.
+    ///
+    /// A borrowed slice without consecutive repeated elements is returned unchanged,
+    /// without allocating.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let unique: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 2, 1]);
+    /// assert!(matches!(unique.deduped(), RefOrBox::Borrowed(_)));
+    ///
+    /// let repeated: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 1, 2]);
+    /// assert_eq!(&[1, 2], &*repeated.deduped());
+    /// ```
+    pub fn deduped(self) -> Self where T: PartialEq {
+        if !self.windows(2).any(|pair| pair[0] == pair[1]) {
+            return self;
+        }
+        let mut owned_value = match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.to_vec(),
+            Self::Owned(owned_value) => owned_value.into_vec()
+        };
+        owned_value.dedup();
+        Self::Owned(owned_value.into_boxed_slice())
+    }
+
+    /// Retains only the elements specified by the predicate, cloning the slice only
+    /// if it is borrowed and some element is removed.
+    ///
+    /// The predicate is called exactly once for each element, in order. If every
+    /// element passes, a borrowed slice stays borrowed.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let mut all_even: RefOrBox<[u8]> = RefOrBox::Borrowed(&[2, 4]);
+    /// all_even.retain_cow(|value| value % 2 == 0);
+    /// assert!(matches!(all_even, RefOrBox::Borrowed(_)));
+    ///
+    /// let mut mixed: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 2, 3, 4]);
+    /// mixed.retain_cow(|value| value % 2 == 0);
+    /// assert_eq!(&[2, 4], &*mixed);
+    /// ```
+    pub fn retain_cow<F>(&mut self, mut predicate: F) where F: FnMut(&T) -> bool {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                let borrowed_value: &'t [T] = borrowed_value;
+                if let Some(removed) = borrowed_value.iter().position(|element| !predicate(element)) {
+                    let mut owned_value = borrowed_value[..removed].to_vec();
+                    owned_value.extend(borrowed_value[removed + 1..].iter().filter(|element| predicate(element)).cloned());
+                    *self = Self::Owned(owned_value.into_boxed_slice());
+                }
+            },
+            Self::Owned(owned_value) => {
+                let mut retained = mem::take(owned_value).into_vec();
+                retained.retain(predicate);
+                *owned_value = retained.into_boxed_slice();
+            }
+        }
+    }
+
+    /// Obtains a subslice which keeps the original lifetime.
+    ///
+    /// If the data is borrowed, the result borrows from the original slice, so that
+    /// it may outlive this wrapper. If the data is owned, the subslice is cloned.
+    /// Returns `None` if the range is out of bounds.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// fn header(packet: &RefOrBox<'static, [u8]>) -> Option<RefOrBox<'static, [u8]>> {
+    ///     packet.get_ref(..2)
+    /// }
+    ///
+    /// let header = header(&RefOrBox::Borrowed(&[1, 2, 3])).unwrap();
+    /// assert!(matches!(header, RefOrBox::Borrowed(&[1, 2])));
+    /// ```
+    pub fn get_ref<R>(&self, range: R) -> Option<RefOrBox<'t, [T]>> where R: SliceIndex<[T], Output = [T]> {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                let borrowed_value: &'t [T] = borrowed_value;
+                borrowed_value.get(range).map(RefOrBox::Borrowed)
+            },
+            Self::Owned(owned_value) => owned_value.get(range).map(|subslice| RefOrBox::Owned(subslice.into()))
+        }
+    }
+}
+
+impl<'t> RefOrBox<'t, str> {
+    /// Obtains an owned boxed string, using fallible allocation.
+    ///
+    /// If the data is borrowed, it will be copied into a new allocation.
+    /// If the data is owned, the owned box will be moved out.
+    ///
+    /// Unlike an infallible copy, this reports allocation failure as an error
+    /// rather than aborting the process.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let borrowed: RefOrBox<str> = RefOrBox::Borrowed("text");
+    /// assert_eq!("text", &*borrowed.try_into_owned_fallible().unwrap());
+    /// ```
+    pub fn try_into_owned_fallible(self) -> Result<Box<str>, TryReserveError> {
+        match self {
+            Self::Borrowed(borrowed_value) => try_clone_str(borrowed_value),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+
+    /// Obtains a mutable reference to the owned string, using fallible allocation.
+    ///
+    /// If the data is borrowed, it will first be copied into the Owned variant.
+    /// If allocation fails, the error is returned and the data stays borrowed.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let mut text: RefOrBox<str> = RefOrBox::Borrowed("text");
+    /// text.try_to_mut().unwrap().make_ascii_uppercase();
+    /// assert_eq!("TEXT", &*text);
+    /// ```
+    pub fn try_to_mut(&mut self) -> Result<&mut str, TryReserveError> {
+        if let Self::Borrowed(borrowed_value) = self {
+            *self = Self::Owned(try_clone_str(borrowed_value)?);
+        }
+        match self {
+            Self::Borrowed(_) => unreachable!(),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+
+    /// Retains only the characters specified by the predicate, copying the string
+    /// only if it is borrowed and some character is removed.
+    ///
+    /// The predicate is called exactly once for each character, in order. If every
+    /// character passes, a borrowed string stays borrowed.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let mut trimmed: RefOrBox<str> = RefOrBox::Borrowed("no_spaces");
+    /// trimmed.retain_cow(|c| c != ' ');
+    /// assert!(matches!(trimmed, RefOrBox::Borrowed(_)));
+    ///
+    /// let mut spaced: RefOrBox<str> = RefOrBox::Borrowed("a b c");
+    /// spaced.retain_cow(|c| c != ' ');
+    /// assert_eq!("abc", &*spaced);
+    /// ```
+    pub fn retain_cow<F>(&mut self, mut predicate: F) where F: FnMut(char) -> bool {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                let borrowed_value: &'t str = borrowed_value;
+                if let Some((removed, character)) = borrowed_value.char_indices().find(|(_, character)| !predicate(*character)) {
+                    let mut owned_value = String::from(&borrowed_value[..removed]);
+                    owned_value.extend(borrowed_value[removed + character.len_utf8()..].chars().filter(|character| predicate(*character)));
+                    *self = Self::Owned(owned_value.into_boxed_str());
+                }
+            },
+            Self::Owned(owned_value) => {
+                let mut retained = String::from(mem::take(owned_value));
+                retained.retain(predicate);
+                *owned_value = retained.into_boxed_str();
+            }
+        }
+    }
+
+    /// Obtains a substring which keeps the original lifetime.
+    ///
+    /// If the data is borrowed, the result borrows from the original string, so that
+    /// it may outlive this wrapper. If the data is owned, the substring is copied.
+    /// Returns `None` if the range is out of bounds or not on character boundaries.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// let text: RefOrBox<str> = RefOrBox::Borrowed("key=value");
+    /// assert_eq!("key", &*text.get_ref(..3).unwrap());
+    /// ```
+    pub fn get_ref<R>(&self, range: R) -> Option<RefOrBox<'t, str>> where R: SliceIndex<str, Output = str> {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                let borrowed_value: &'t str = borrowed_value;
+                borrowed_value.get(range).map(RefOrBox::Borrowed)
+            },
+            Self::Owned(owned_value) => owned_value.get(range).map(|substring| RefOrBox::Owned(substring.into()))
+        }
+    }
+
+    /// Finds the first occurrence of `needle`, and obtains the rest of the string
+    /// starting at that occurrence. The result keeps the original lifetime.
+    ///
+    /// If the data is borrowed, the result borrows from the original string, so that
+    /// it may outlive this wrapper. If the data is owned, the result is copied.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// fn query(url: &RefOrBox<'static, str>) -> Option<RefOrBox<'static, str>> {
+    ///     url.find_ref("?")
+    /// }
+    ///
+    /// let query = query(&RefOrBox::Borrowed("/search?q=rust")).unwrap();
+    /// assert!(matches!(query, RefOrBox::Borrowed("?q=rust")));
+    /// ```
+    pub fn find_ref(&self, needle: &str) -> Option<RefOrBox<'t, str>> {
+        self.find(needle).and_then(|start| self.get_ref(start..))
+    }
+}
+
+impl<'t, T: Clone> RefOrOwned<'t, Vec<T>> {
+    /// Appends the elements of another wrapper, reusing an owned buffer where possible.
+    ///
+    /// If `self` is owned, the elements of `other` are added to its buffer, unless only
+    /// `other` has the spare capacity to hold both. If `other` is owned and `self` is not,
+    /// the buffer of `other` is taken over and the elements of `self` are cloned in front
+    /// of it. Owned elements are moved rather than cloned. If either side is empty, no
+    /// allocation takes place, and the other side is kept as is.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let first = vec![1, 2];
+    /// let mut list = RefOrOwned::Borrowed(&first);
+    /// list.append(RefOrOwned::Owned(vec![3]));
+    /// assert_eq!(&[1, 2, 3], &list[..]);
+    /// ```
+    pub fn append(&mut self, other: RefOrOwned<'t, Vec<T>>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        let combined = match (mem::replace(self, Self::Owned(Vec::new())), other) {
+            (Self::Owned(mut owned_value), Self::Owned(mut other_owned)) => {
+                if owned_value.capacity() - owned_value.len() < other_owned.len()
+                        && other_owned.capacity() - other_owned.len() >= owned_value.len() {
+                    other_owned.splice(0..0, owned_value);
+                    other_owned
+                } else {
+                    owned_value.append(&mut other_owned);
+                    owned_value
+                }
+            },
+            (Self::Owned(mut owned_value), Self::Borrowed(other_borrowed)) => {
+                owned_value.extend_from_slice(other_borrowed);
+                owned_value
+            },
+            (Self::Borrowed(borrowed_value), Self::Owned(mut other_owned)) => {
+                other_owned.splice(0..0, borrowed_value.iter().cloned());
+                other_owned
+            },
+            (Self::Borrowed(borrowed_value), Self::Borrowed(other_borrowed)) => {
+                let mut combined = Vec::with_capacity(borrowed_value.len() + other_borrowed.len());
+                combined.extend_from_slice(borrowed_value);
+                combined.extend_from_slice(other_borrowed);
+                combined
+            }
+        };
+        *self = Self::Owned(combined);
+    }
+}
+
+impl<'t> RefOrOwned<'t, String> {
+    /// Appends another string wrapper, reusing an owned buffer where possible.
+    ///
+    /// If `self` is owned, `other` is added to its buffer, unless only `other` has the
+    /// spare capacity to hold both. If `other` is owned and `self` is not, the buffer of
+    /// `other` is taken over and `self` is copied in front of it. If either side is empty,
+    /// no allocation takes place, and the other side is kept as is.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let hello = String::from("Hello, ");
+    /// let mut greeting = RefOrOwned::Borrowed(&hello);
+    /// greeting.push_str_or(RefOrOwned::Owned(String::from("world")));
+    /// assert_eq!("Hello, world", &*greeting);
+    /// ```
+    pub fn push_str_or(&mut self, other: RefOrOwned<'t, String>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        let combined = match (mem::replace(self, Self::Owned(String::new())), other) {
+            (Self::Owned(mut owned_value), Self::Owned(mut other_owned)) => {
+                if owned_value.capacity() - owned_value.len() < other_owned.len()
+                        && other_owned.capacity() - other_owned.len() >= owned_value.len() {
+                    other_owned.insert_str(0, &owned_value);
+                    other_owned
+                } else {
+                    owned_value.push_str(&other_owned);
+                    owned_value
+                }
+            },
+            (Self::Owned(mut owned_value), Self::Borrowed(other_borrowed)) => {
+                owned_value.push_str(other_borrowed);
+                owned_value
+            },
+            (Self::Borrowed(borrowed_value), Self::Owned(mut other_owned)) => {
+                other_owned.insert_str(0, borrowed_value);
+                other_owned
+            },
+            (Self::Borrowed(borrowed_value), Self::Borrowed(other_borrowed)) => {
+                let mut combined = String::with_capacity(borrowed_value.len() + other_borrowed.len());
+                combined.push_str(borrowed_value);
+                combined.push_str(other_borrowed);
+                combined
+            }
+        };
+        *self = Self::Owned(combined);
+    }
+}
+
+impl<'t> RefOrBox<'t, [u8]> {
+    /// Views a sequence of byte buffers as `IoSlice`s for vectored writes.
+    ///
+    /// Borrowed and owned buffers may be mixed, and none of them is copied.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrBox;
+    /// use std::io::Write;
+    ///
+    /// let body = String::from("body");
+    /// let buffers = [
+    ///     RefOrBox::Borrowed(&b"header\n"[..]),
+    ///     RefOrBox::Owned(body.into_bytes().into_boxed_slice())
+    /// ];
+    ///
+    /// let mut output = Vec::new();
+    /// output.write_vectored(&RefOrBox::as_io_slices(&buffers)).unwrap();
+    /// assert_eq!(b"header\nbody", &output[..]);
+    /// ```
+    pub fn as_io_slices<'b>(buffers: &'b [Self]) -> Vec<IoSlice<'b>> {
+        buffers.iter().map(|buffer| IoSlice::new(buffer)).collect()
+    }
+}
+
+impl<'t, T> RefMutOrBox<'t, [T]> {
+    /// Divides the slice into two at an index.
+    ///
+    /// If the data is borrowed, the slice is split in place, and both halves
+    /// borrow from it. If the data is owned, the elements after `mid` are moved
+    /// into a new allocation, and both halves are owned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`, like `slice::split_at_mut`.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefMutOrBox;
+    /// let mut data = [1, 2, 3, 4];
+    /// let (mut left, mut right) = RefMutOrBox::from(&mut data[..]).split_at_mut(1);
+    /// left[0] = 5;
+    /// right[0] = 6;
+    /// assert_eq!([5, 6, 3, 4], data);
+    /// ```
+    pub fn split_at_mut(self, mid: usize) -> (Self, Self) {
+        match self {
+            Self::Borrowed(borrowed_value) => {
+                let (left, right) = borrowed_value.split_at_mut(mid);
+                (Self::Borrowed(left), Self::Borrowed(right))
+            },
+            Self::Owned(owned_value) => {
+                let mut left = owned_value.into_vec();
+                let right = left.split_off(mid);
+                (Self::Owned(left.into_boxed_slice()), Self::Owned(right.into_boxed_slice()))
+            }
+        }
+    }
+}
@@ -15,10 +15,18 @@
  */
 
 use crate::ref_or_owned::*;
+use crate::test_support::block_on;
+use crate::dyn_cmp::AsAny;
 use std::cell::RefCell;
 use std::error::Error;
 use downcast_rs::{Downcast, impl_downcast};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::rc::Rc;
+use std::ops::Bound;
+use std::future::{self, Future, IntoFuture, Ready};
+use std::panic::{self, AssertUnwindSafe, RefUnwindSafe, UnwindSafe};
 
 trait MyTrait: Downcast {
     fn do_something(&self);
@@ -229,6 +237,282 @@ fn ref_or_box_into_owned() {
     let _cloned: Box<dyn CloneTrait> = clonable.into_owned();
 }
 
+#[test]
+fn ref_or_owned_clone() {
+    let shared = vec![1];
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    assert!(std::ptr::eq(&shared, &*borrowed.clone()));
+    let owned: RefOrOwned<Vec<u8>> = RefOrOwned::Owned(vec![2]);
+    let cloned = owned.clone();
+    assert!(cloned.is_owned());
+    assert_eq!(owned, cloned);
+}
+
+#[test]
+fn ref_or_owned_into_box() {
+    let shared = vec![1];
+    let boxed = RefOrOwned::Borrowed(&shared).into_box();
+    assert_eq!(shared, *boxed);
+    assert_eq!(Box::new(vec![2]), RefOrOwned::Owned(vec![2]).into_box());
+}
+
+#[test]
+fn try_as_mut_when_owned() {
+    let shared = Bean::new(1);
+    assert!(RefOrOwned::Borrowed(&shared).try_as_mut().is_none());
+    let mut owned = RefOrOwned::Owned(Bean::new(2));
+    owned.try_as_mut().unwrap().data = 3;
+    assert_eq!(3, owned.data);
+
+    let mut boxed: RefOrBox<dyn MyTrait> = RefOrBox::Owned(Box::new(Implementor::default()));
+    boxed.try_as_mut().unwrap().do_mutable();
+    let implementor = Implementor::default();
+    let mut boxed: RefOrBox<dyn MyTrait> = RefOrBox::Borrowed(&implementor);
+    assert!(boxed.try_as_mut().is_none());
+}
+
+#[test]
+fn ref_or_owned_copy() {
+    fn sum(first: RefOrOwned<u32>, second: RefOrOwned<u32>) -> u32 {
+        *first + *second
+    }
+
+    let shared = 3;
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    let owned = RefOrOwned::Owned(4);
+    assert_eq!(6, sum(borrowed, borrowed));
+    assert_eq!(8, sum(owned, owned));
+    assert_eq!(7, sum(borrowed, owned));
+    assert!(borrowed.is_borrowed());
+    assert!(owned.is_owned());
+}
+
+#[test]
+#[cfg(feature = "trait-clone")]
+fn ref_or_box_clone() {
+    let clonable = ClonableStruct::default();
+    let borrowed: RefOrBox<dyn CloneTrait> = RefOrBox::Borrowed(&clonable);
+    assert!(borrowed.clone().is_borrowed());
+    let owned: RefOrBox<str> = RefOrBox::Owned(Box::from("owned"));
+    let cloned = owned.clone();
+    assert!(cloned.is_owned());
+    assert_eq!(owned, cloned);
+}
+
+#[test]
+fn cloned_snapshots() {
+    let shared = vec![1];
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    let snapshot = borrowed.cloned();
+    assert!(snapshot.is_owned() && borrowed.is_borrowed());
+    assert_eq!(borrowed, snapshot);
+
+    let mut list = vec![2];
+    let mutable = RefMutOrOwned::Borrowed(&mut list);
+    let mut snapshot = mutable.cloned();
+    snapshot.push(3);
+    assert_eq!(vec![2], *mutable);
+}
+
+#[test]
+#[cfg(feature = "trait-clone")]
+fn ref_or_box_cloned() {
+    let clonable = ClonableStruct::default();
+    let borrowed: RefOrBox<dyn CloneTrait> = RefOrBox::Borrowed(&clonable);
+    assert!(borrowed.cloned().is_owned());
+
+    let mut array = [1, 2];
+    let mutable: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    let mut snapshot = mutable.cloned();
+    snapshot[0] = 3;
+    assert_eq!([1, 2], *mutable);
+}
+
+#[test]
+fn into_static_erases_lifetime() {
+    fn send(value: RefOrOwned<'static, Vec<u8>>) -> Vec<u8> {
+        std::thread::spawn(move || value.to_vec()).join().unwrap()
+    }
+
+    let temporary = vec![1, 2];
+    assert_eq!(temporary, send(RefOrOwned::Borrowed(&temporary).into_static()));
+    assert_eq!(vec![3], send(RefOrOwned::Owned(vec![3]).into_static()));
+
+    let mut temporary = ClonableStruct::default();
+    let stored: RefMutOrOwned<'static, ClonableStruct> = RefMutOrOwned::Borrowed(&mut temporary).into_static();
+    assert!(stored.is_owned());
+}
+
+#[test]
+#[cfg(feature = "trait-clone")]
+fn ref_or_box_into_static() {
+    let temporary = String::from("temporary");
+    let stored: RefOrBox<'static, str> = RefOrBox::Borrowed(temporary.as_str()).into_static();
+    drop(temporary);
+    assert_eq!("temporary", &*stored);
+
+    let clonable = ClonableStruct::default();
+    let stored: RefOrBox<'static, dyn CloneTrait> = RefOrBox::Borrowed(&clonable as &dyn CloneTrait).into_static();
+    assert!(stored.is_owned());
+}
+
+#[test]
+fn leak_box_wrappers() {
+    let interned: &'static str = RefOrBox::<'static, str>::Owned(Box::from("interned")).leak();
+    assert_eq!("interned", interned);
+    let shared = Implementor::default();
+    let borrowed: RefOrBox<dyn MyTrait> = RefOrBox::Borrowed(&shared);
+    assert!(std::ptr::eq(&shared as &dyn MyTrait, borrowed.leak()));
+
+    let counter: &'static mut [u8] = RefMutOrBox::<'static, [u8]>::Owned(Box::new([0])).leak();
+    counter[0] += 1;
+    assert_eq!([1], *counter);
+}
+
+#[test]
+fn ref_or_box_into_owned_value() {
+    use std::path::{Path, PathBuf};
+
+    let text: RefOrBox<str> = RefOrBox::Owned(Box::from("text"));
+    assert_eq!(String::from("text"), text.into_owned_value());
+    let mut array = [1, 2];
+    let slice: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    assert_eq!(vec![1, 2], slice.into_owned_value());
+    let path: RefOrBox<Path> = RefOrBox::Borrowed(Path::new("/tmp"));
+    assert_eq!(PathBuf::from("/tmp"), path.into_owned_value());
+}
+
+#[test]
+fn try_clone_into_owned() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let duplicate = RefOrOwned::Borrowed(&listener).try_clone_into_owned().unwrap();
+    assert_eq!(listener.local_addr().unwrap(), duplicate.local_addr().unwrap());
+    assert!(RefOrBox::Borrowed(&listener).try_clone_into_owned().is_ok());
+
+    struct Exhausted;
+    impl TryClone for Exhausted {
+        fn try_clone(&self) -> Result<Self, TryCloneError> {
+            Err(TryCloneError::new("exhausted"))
+        }
+    }
+    let error = RefOrOwned::Borrowed(&Exhausted).try_clone_into_owned().err().unwrap();
+    assert_eq!("Failed to clone value: exhausted", error.to_string());
+    assert!(RefOrBox::Owned(Box::new(Exhausted)).try_clone_into_owned().is_ok());
+}
+
+#[test]
+fn try_into_box() {
+    let mut implementor = Implementor::default();
+    let borrowed: RefMutOrBox<dyn MyTrait> = RefMutOrBox::Borrowed(&mut implementor);
+    let borrowed = match borrowed.try_into_box() {
+        Err(borrowed) => borrowed,
+        Ok(_) => panic!("Borrowed value extracted as Box")
+    };
+    assert!(matches!(borrowed, RefMutOrBox::Borrowed(_)));
+
+    let owned: RefOrBox<dyn MyTrait> = RefOrBox::Owned(Box::new(Implementor::default()));
+    let owned = owned.try_into_box().ok().expect("Owned value is a Box");
+    assert!(owned.downcast_ref::<Implementor>().is_some());
+}
+
+#[test]
+fn same_impl() {
+    trait Shape: AsAny {}
+    struct Circle;
+    struct Square;
+    impl Shape for Circle {}
+    impl Shape for Square {}
+
+    let circle = Circle;
+    let borrowed: RefOrBox<dyn Shape> = RefOrBox::Borrowed(&circle);
+    let owned: RefOrBox<dyn Shape> = RefOrBox::Owned(Box::new(Circle));
+    let other: RefOrBox<dyn Shape> = RefOrBox::Owned(Box::new(Square));
+    assert!(borrowed.same_impl(&owned));
+    assert!(!borrowed.same_impl(&other));
+    assert!(owned.same_impl(&RefOrBox::Borrowed(&circle)));
+
+    let mut circle = Circle;
+    let borrowed_mut: RefMutOrBox<dyn Shape> = RefMutOrBox::Borrowed(&mut circle);
+    assert!(!borrowed_mut.same_impl(&RefMutOrBox::<dyn Shape>::Owned(Box::new(Square))));
+    assert!(RefOrBox::Owned(Box::new(5_u8)).same_impl(&RefOrBox::Borrowed(&6_u8)));
+}
+
+#[test]
+fn unsize_into_trait_objects() {
+    let implementor = Implementor::default();
+    let borrowed: RefOrBox<dyn MyTrait> = RefOrOwned::Borrowed(&implementor).unsize(|r| r as _, |b| b as _);
+    assert!(matches!(borrowed, RefOrBox::Borrowed(_)));
+    borrowed.do_something();
+    assert_eq!(1, implementor.calls());
+
+    let owned: RefOrBox<dyn MyTrait> = RefOrOwned::Owned(Implementor::default()).unsize::<dyn MyTrait, _, _>(|r| r, |b| b);
+    assert!(owned.downcast_ref::<Implementor>().is_some());
+
+    let mut implementor = Implementor::default();
+    let mut borrowed_mut: RefMutOrBox<dyn MyTrait> = RefMutOrOwned::Borrowed(&mut implementor).unsize(|r| r as _, |b| b as _);
+    borrowed_mut.do_mutable();
+    assert_eq!(1, implementor.mut_calls());
+}
+
+//
+// OptionRefOrOwnedExt
+//
+
+#[test]
+fn option_get_or_insert_borrowed() {
+    let first = Bean::new(1);
+    let second = Bean::new(2);
+    let mut option: Option<RefOrOwned<Bean>> = None;
+    assert_eq!(1, option.get_or_insert_borrowed(&first).data);
+    assert_eq!(1, option.get_or_insert_borrowed(&second).data);
+    assert!(matches!(option, Some(RefOrOwned::Borrowed(_))));
+}
+
+#[test]
+fn option_get_or_insert_owned_with() {
+    let existing = Bean::new(3);
+    let mut option = Some(RefOrOwned::Borrowed(&existing));
+    assert_eq!(3, option.get_or_insert_owned_with(|| panic!("Should not be called")).data);
+
+    let mut option: Option<RefOrOwned<Bean>> = None;
+    assert_eq!(4, option.get_or_insert_owned_with(|| Bean::new(4)).data);
+    assert!(matches!(option, Some(RefOrOwned::Owned(_))));
+}
+
+//
+// IntoVariant and PartitionVariants
+//
+
+#[test]
+fn partition_ref_or_owned_variants() {
+    let first = Bean::new(1);
+    let second = Bean::new(2);
+    let items = vec![
+        RefOrOwned::Borrowed(&first),
+        RefOrOwned::Owned(Bean::new(3)),
+        RefOrOwned::Borrowed(&second)
+    ];
+    let (borrowed, owned) = items.into_iter().partition_variants();
+    assert!(borrowed == vec![&first, &second]);
+    assert!(owned == vec![Bean::new(3)]);
+}
+
+#[test]
+fn partition_ref_mut_or_box_variants() {
+    let mut implementor = Implementor::default();
+    let items: Vec<RefMutOrBox<dyn MyTrait>> = vec![
+        RefMutOrBox::Owned(Box::new(Implementor::default())),
+        RefMutOrBox::Borrowed(&mut implementor)
+    ];
+    let (borrowed, owned) = items.into_iter().partition_variants();
+    assert_eq!(1, borrowed.len());
+    assert_eq!(1, owned.len());
+    for borrowed_value in borrowed {
+        borrowed_value.do_mutable();
+    }
+    assert_eq!(1, implementor.mut_calls());
+}
+
 //
 // Deref, AsRef, AsMut, Borrow, and BorrowMut
 //
@@ -424,3 +708,1570 @@ fn ref_mut_or_box_std_traits() {
     assert_eq!(Ordering::Less, eval_partial_ord(&generated, &incremented));
     assert_eq!(Ordering::Greater, eval_partial_ord(&incremented, &generated));
 }
+
+#[test]
+fn ref_or_box_ord() {
+    let first: RefOrBox<str> = RefOrBox::Borrowed("first");
+    let second: RefOrBox<str> = RefOrBox::Owned("second".into());
+    assert!(eval_eq(&first, &first));
+    assert_eq!(Ordering::Less, eval_ord(&first, &second));
+
+    let mut third = Bean::new(3);
+    let third = RefMutOrBox::Borrowed(&mut third);
+    assert_eq!(Ordering::Greater, eval_ord(&third, &RefMutOrBox::Owned(Box::new(Bean::new(2)))));
+}
+
+#[test]
+#[cfg(not(feature = "transparent-debug"))]
+fn debug_shows_variant() {
+    let value = vec![1, 2];
+    assert_eq!("Borrowed([1, 2])", format!("{:?}", RefOrOwned::Borrowed(&value)));
+    assert_eq!("Owned(\"text\")", format!("{:?}", RefOrBox::<str>::Owned("text".into())));
+    assert_eq!("Shared(3)", format!("{:?}", ArcOrOwned::Shared(Arc::new(3))));
+    assert_eq!("Owned(\n    3,\n)", format!("{:#?}", RefMutOrOwned::Owned(3)));
+}
+
+#[test]
+fn numeric_formatting_passthrough() {
+    let value = 255_u32;
+    let mut mutable = 8_u8;
+    assert_eq!("ff", format!("{:x}", RefOrOwned::Borrowed(&value)));
+    assert_eq!("0xFF", format!("{:#X}", RefOrBox::Owned(Box::new(value))));
+    assert_eq!("10", format!("{:o}", RefMutOrOwned::Borrowed(&mut mutable)));
+    assert_eq!("00001000", format!("{:08b}", RefMutOrBox::Owned(Box::new(8_u8))));
+    assert_eq!("1.5e3", format!("{:e}", RefOrOwned::Owned(1500.0)));
+    assert_eq!("1.5E3", format!("{:E}", ArcOrOwned::Owned(1500.0)));
+}
+
+#[test]
+fn pointer_formatting() {
+    let value = String::from("value");
+    let borrowed = RefOrOwned::Borrowed(&value);
+    assert_eq!(format!("{:p}", &value), format!("{:p}", borrowed));
+
+    let owned: RefOrBox<str> = RefOrBox::Owned(Box::from("value"));
+    let view = owned.as_borrowed();
+    assert_eq!(format!("{:p}", owned), format!("{:p}", view));
+    assert_ne!(format!("{:p}", owned), format!("{:p}", RefOrBox::Borrowed(value.as_str())));
+
+    let mut bytes = [1_u8, 2];
+    let expected = format!("{:p}", &bytes as *const [u8; 2]);
+    let mutable: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut bytes);
+    assert_eq!(expected, format!("{:p}", mutable));
+}
+
+fn sum_all<I: Iterator<Item = u32>>(iter: I) -> u32 {
+    iter.sum()
+}
+
+#[test]
+fn iterator_passthrough() {
+    let mut borrowed_iter = vec![1, 2, 3].into_iter();
+    let mut wrapper = RefMutOrOwned::Borrowed(&mut borrowed_iter);
+    assert_eq!(Some(1), wrapper.next());
+    assert_eq!((2, Some(2)), wrapper.size_hint());
+    assert_eq!(5, sum_all(wrapper));
+    assert_eq!(None, borrowed_iter.next());
+
+    assert_eq!(10, sum_all(RefMutOrOwned::Owned(1..5)));
+    let boxed: RefMutOrBox<dyn Iterator<Item = u32>> = RefMutOrBox::Owned(Box::new(1..5));
+    assert_eq!(10, sum_all(boxed));
+
+    let mut range = 0..10;
+    let mut borrowed: RefMutOrBox<dyn Iterator<Item = u32>> = RefMutOrBox::Borrowed(&mut range);
+    assert_eq!(Some(3), borrowed.nth(3));
+    assert_eq!(Some(4), range.next());
+}
+
+#[test]
+fn iterator_adapters_on_wrapper() {
+    let mut iter = vec![1, 2, 3].into_iter();
+    let doubled: Vec<u32> = RefMutOrOwned::Borrowed(&mut iter).map(|x| x * 2).collect();
+    assert_eq!(vec![2, 4, 6], doubled);
+
+    let boxed: RefMutOrBox<dyn Iterator<Item = u32>> = RefMutOrBox::Owned(Box::new(1..4));
+    assert_eq!(vec![3, 4, 5], boxed.map(|x| x + 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn double_ended_and_exact_size_passthrough() {
+    fn assert_fused<I: std::iter::FusedIterator>(_iter: &I) {}
+
+    let mut iter = vec![1, 2, 3, 4].into_iter();
+    let mut wrapper = RefMutOrOwned::Borrowed(&mut iter);
+    assert_eq!(4, wrapper.len());
+    assert_eq!(Some(4), wrapper.next_back());
+    assert_fused(&wrapper);
+    assert_eq!(vec![3, 2], wrapper.rev().take(2).collect::<Vec<_>>());
+    assert_eq!(Some(1), iter.next());
+
+    let mut boxed: RefMutOrBox<dyn DoubleEndedIterator<Item = u32>> = RefMutOrBox::Owned(Box::new(1..5));
+    assert_eq!(Some(3), boxed.nth_back(1));
+    assert_eq!(vec![2, 1], boxed.rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn iter_cloned_leaves_lender_untouched() {
+    let source = [1, 2, 3];
+    let lent = source.iter();
+    let borrowed = RefOrOwned::Borrowed(&lent);
+    assert_eq!(vec![&1, &2, &3], borrowed.iter_cloned().collect::<Vec<_>>());
+    assert_eq!(3, lent.len());
+    assert_eq!(6, sum_all(RefOrOwned::Owned(1..4).iter_cloned()));
+}
+
+#[test]
+fn element_wise_into_iter() {
+    let shared = vec![Bean::new(1), Bean::new(2)];
+    let elements: Vec<RefOrOwned<Bean>> = RefOrOwned::Borrowed(&shared).into_iter().collect();
+    assert!(elements.iter().all(RefOrOwned::is_borrowed));
+    assert!(std::ptr::eq(&shared[1], &*elements[1]));
+
+    let owned: RefOrBox<[Bean]> = RefOrBox::Owned(Box::new([Bean::new(3), Bean::new(4)]));
+    let mut iter = owned.into_iter();
+    assert_eq!(2, iter.len());
+    let last = iter.next_back().unwrap();
+    assert!(last.is_owned());
+    assert_eq!(4, last.data);
+
+    let datas: Vec<u8> = RefOrOwned::Owned(vec![Bean::new(5)]).into_iter().map(|bean| bean.data).collect();
+    assert_eq!(vec![5], datas);
+}
+
+#[test]
+fn collect_into_wrappers() {
+    let collected: RefOrOwned<Vec<u8>> = (1..4).collect();
+    assert!(collected.is_owned());
+    assert_eq!(vec![1, 2, 3], *collected);
+
+    let collected: RefOrBox<[String]> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    assert!(collected.is_owned());
+    assert_eq!(["a", "b"], *collected);
+}
+
+fn append_all<E: Extend<u8>>(mut target: E) -> E {
+    target.extend([1, 2]);
+    target
+}
+
+#[test]
+fn extend_passthrough() {
+    let mut list = vec![0];
+    append_all(RefMutOrOwned::Borrowed(&mut list));
+    assert_eq!(vec![0, 1, 2], list);
+    assert_eq!(vec![1, 2], *append_all(RefMutOrOwned::Owned(Vec::new())));
+
+    let mut text = String::from("a");
+    let mut wrapper = RefMutOrBox::Borrowed(&mut text);
+    wrapper.extend(['b', 'c']);
+    assert_eq!("abc", text);
+}
+
+trait Opaque {}
+
+impl Opaque for u8 {}
+
+crate::impl_opaque_debug!(Opaque);
+
+#[test]
+fn debug_trait_object_without_debug() {
+    let borrowed: RefOrBox<dyn Opaque> = RefOrBox::Borrowed(&1_u8);
+    let mut value = 2_u8;
+    let mutable: RefMutOrBox<dyn Opaque> = RefMutOrBox::Borrowed(&mut value);
+    let name = std::any::type_name::<dyn Opaque>();
+    if cfg!(feature = "transparent-debug") {
+        assert_eq!(name, format!("{:?}", borrowed));
+    } else {
+        assert_eq!(format!("Borrowed({})", name), format!("{:?}", borrowed));
+        assert_eq!(format!("Borrowed({})", name), format!("{:?}", mutable));
+        assert_eq!("Owned([1, 2])", format!("{:?}", RefOrBox::<[u8]>::Owned(Box::new([1, 2]))));
+    }
+}
+
+#[test]
+#[cfg(feature = "transparent-debug")]
+fn debug_is_transparent() {
+    let value = vec![1, 2];
+    assert_eq!("[1, 2]", format!("{:?}", RefOrOwned::Borrowed(&value)));
+    assert_eq!("\"text\"", format!("{:?}", RefOrBox::<str>::Owned("text".into())));
+    assert_eq!("3", format!("{:?}", ArcOrOwned::Shared(Arc::new(3))));
+    assert_eq!("[\n    1,\n    2,\n]", format!("{:#?}", RefMutOrOwned::Owned(value)));
+}
+
+#[cfg(feature = "fast-ptr-eq")]
+struct CountingEq {
+    calls: std::cell::Cell<u8>
+}
+
+#[cfg(feature = "fast-ptr-eq")]
+impl PartialEq for CountingEq {
+    fn eq(&self, _other: &Self) -> bool {
+        self.calls.set(self.calls.get() + 1);
+        false
+    }
+}
+
+#[test]
+#[cfg(feature = "fast-ptr-eq")]
+fn pointer_equality_fast_path() {
+    let value = CountingEq { calls: std::cell::Cell::new(0) };
+    assert!(RefOrOwned::Borrowed(&value) == RefOrOwned::Borrowed(&value));
+    assert!(RefOrBox::Borrowed(&value) == RefOrOwned::Borrowed(&value));
+    assert_eq!(0, value.calls.get());
+
+    let other = CountingEq { calls: std::cell::Cell::new(0) };
+    assert!(RefOrOwned::Borrowed(&value) != RefOrOwned::Borrowed(&other));
+    assert_eq!(1, value.calls.get());
+
+    let data = [1.0, f64::NAN];
+    let slice: RefOrBox<[f64]> = RefOrBox::Borrowed(&data);
+    assert!(slice == RefOrBox::Borrowed(&data[..]));
+    assert!(slice != RefOrBox::Borrowed(&data[..1]));
+}
+
+//
+// BTreeMap keys with borrowed lookups
+//
+
+#[test]
+fn btree_map_ref_or_owned_keys() {
+    let borrowed_key = Bean::new(1);
+    let mut map = BTreeMap::new();
+    map.insert(RefOrOwned::Borrowed(&borrowed_key), "borrowed");
+    map.insert(RefOrOwned::Owned(Bean::new(3)), "owned");
+    map.insert(RefOrOwned::Owned(Bean::new(5)), "another");
+
+    assert_eq!(Some(&"borrowed"), map.get(&Bean::new(1)));
+    assert_eq!(Some(&"owned"), map.get(&Bean::new(3)));
+    assert_eq!(None, map.get(&Bean::new(4)));
+
+    let in_range: Vec<&str> = map.range(Bean::new(2)..).map(|(_, value)| *value).collect();
+    assert_eq!(vec!["owned", "another"], in_range);
+}
+
+#[test]
+fn btree_map_str_keys() {
+    let borrowed_key = String::from("banana");
+    let mut map: BTreeMap<RefOrBox<str>, u8> = BTreeMap::new();
+    map.insert(RefOrBox::Borrowed(&borrowed_key), 2);
+    map.insert(RefOrBox::Owned("apple".into()), 1);
+    map.insert(RefOrBox::Owned("cherry".into()), 3);
+
+    assert_eq!(Some(&1), map.get("apple"));
+    assert_eq!(Some(&2), map.get("banana"));
+
+    let in_range: Vec<u8> = map
+        .range::<str, _>((Bound::Excluded("apple"), Bound::Included("cherry")))
+        .map(|(_, value)| *value)
+        .collect();
+    assert_eq!(vec![2, 3], in_range);
+}
+
+#[test]
+fn cross_enum_partial_eq() {
+    let mut bean = Bean::new(5);
+    let mut other_bean = Bean::new(5);
+    let ref_or_owned = RefOrOwned::Owned(Bean::new(5));
+    let ref_or_box: RefOrBox<Bean> = RefOrBox::Owned(Box::new(Bean::new(5)));
+    let different: RefOrBox<Bean> = RefOrBox::Owned(Box::new(Bean::new(6)));
+
+    assert!(ref_or_owned == ref_or_box);
+    assert!(ref_or_box == ref_or_owned);
+    assert!(ref_or_owned != different);
+    assert!(RefMutOrOwned::Borrowed(&mut bean) == ref_or_owned);
+    assert!(RefMutOrBox::Borrowed(&mut other_bean) == ref_or_box);
+    assert!(ref_or_box == RefMutOrOwned::Owned(Bean::new(5)));
+
+    let trait_object: Box<dyn BeanTrait> = Box::new(Bean::new(5));
+    let trait_object = RefOrBox::Owned(trait_object);
+    let other_trait_object: Box<dyn BeanTrait> = Box::new(Bean::new(5));
+    assert!(trait_object == RefMutOrBox::Owned(other_trait_object));
+}
+
+//
+// Slice and str specializations
+//
+
+#[test]
+fn fallible_promotion() {
+    let data = [1, 2, 3];
+    let mut slice: RefOrBox<[u8]> = RefOrBox::Borrowed(&data);
+    slice.try_to_mut().expect("Allocation").reverse();
+    assert!(matches!(slice, RefOrBox::Owned(_)));
+    assert_eq!(&[3, 2, 1], &*slice.try_into_owned_fallible().expect("No allocation"));
+
+    let text: RefOrBox<str> = RefOrBox::Borrowed("text");
+    let owned = text.try_into_owned_fallible().expect("Allocation");
+    assert_eq!("text", &*owned);
+}
+
+#[test]
+fn fallible_promotion_zero_sized() {
+    let data = [(), ()];
+    let slice: RefOrBox<[()]> = RefOrBox::Borrowed(&data);
+    assert_eq!(2, slice.try_into_owned_fallible().expect("No allocation").len());
+}
+
+#[test]
+fn sorted_and_deduped() {
+    let data = [1, 1, 2, 3];
+    let sorted = RefOrBox::from(&data[..]).sorted();
+    assert!(matches!(sorted, RefOrBox::Borrowed(_)));
+    let deduped = sorted.deduped();
+    assert!(matches!(deduped, RefOrBox::Owned(_)));
+    assert_eq!(&[1, 2, 3], &*deduped);
+
+    let owned: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([2, 1, 2]));
+    let sorted_deduped = owned.deduped().sorted().deduped();
+    assert_eq!(&[1, 2], &*sorted_deduped);
+
+    let empty: RefOrBox<[u8]> = RefOrBox::Borrowed(&[]);
+    assert!(matches!(empty.sorted().deduped(), RefOrBox::Borrowed(_)));
+}
+
+#[test]
+fn retain_cow() {
+    let data = [1, 2, 3];
+    let mut calls = 0;
+    let mut slice = RefOrBox::from(&data[..]);
+    slice.retain_cow(|_| { calls += 1; true });
+    assert_eq!(3, calls);
+    assert!(matches!(slice, RefOrBox::Borrowed(_)));
+    slice.retain_cow(|value| *value != 2);
+    assert!(matches!(slice, RefOrBox::Owned(_)));
+    slice.retain_cow(|value| *value != 3);
+    assert_eq!(&[1], &*slice);
+
+    let mut text: RefOrBox<str> = RefOrBox::Borrowed("héllo");
+    text.retain_cow(|c| c != 'é');
+    assert_eq!("hllo", &*text);
+    text.retain_cow(|c| c != 'l');
+    assert_eq!("ho", &*text);
+}
+
+#[test]
+fn append_reuses_buffers() {
+    let data = vec![1, 2];
+    let nothing = Vec::new();
+    let mut list = RefOrOwned::Borrowed(&data);
+    list.append(RefOrOwned::Borrowed(&nothing));
+    assert!(matches!(list, RefOrOwned::Borrowed(_)));
+
+    let mut buffer = Vec::with_capacity(16);
+    buffer.push(3);
+    let buffer_ptr = buffer.as_ptr();
+    list.append(RefOrOwned::Owned(buffer));
+    assert_eq!(&[1, 2, 3], &list[..]);
+    assert_eq!(buffer_ptr, list.as_ptr());
+    for _ in 0..4 {
+        list.append(RefOrOwned::Borrowed(&data));
+    }
+    assert_eq!(11, list.len());
+    assert_eq!(buffer_ptr, list.as_ptr());
+
+    let mut empty = RefOrOwned::Borrowed(&nothing);
+    empty.append(RefOrOwned::Borrowed(&data));
+    assert!(matches!(empty, RefOrOwned::Borrowed(_)));
+
+    let start = String::from("a");
+    let mut text = RefOrOwned::Borrowed(&start);
+    let mut buffer = String::with_capacity(16);
+    buffer.push('c');
+    let buffer_ptr = buffer.as_ptr();
+    text.push_str_or(RefOrOwned::Owned(buffer));
+    text.push_str_or(RefOrOwned::Owned(String::from("d")));
+    text.push_str_or(RefOrOwned::Borrowed(&start));
+    assert_eq!("acda", &*text);
+    assert_eq!(buffer_ptr, text.as_ptr());
+}
+
+#[test]
+fn search_keeps_lifetime() {
+    let data = [1, 2, 3];
+    let subslice = {
+        let slice = RefOrBox::from(&data[..]);
+        slice.get_ref(1..).expect("In bounds")
+    };
+    assert!(matches!(subslice, RefOrBox::Borrowed(&[2, 3])));
+    assert!(subslice.get_ref(3..).is_none());
+
+    let owned: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([1, 2]));
+    assert!(matches!(owned.get_ref(..1), Some(RefOrBox::Owned(_))));
+
+    let text = String::from("name: value");
+    let found = {
+        let wrapper: RefOrBox<str> = RefOrBox::Borrowed(&text);
+        wrapper.find_ref(": ").expect("Found")
+    };
+    assert_eq!(": value", &*found);
+    assert!(found.find_ref("missing").is_none());
+    assert!(RefOrBox::<str>::Borrowed("é").get_ref(1..).is_none());
+}
+
+#[test]
+fn vectored_writes() {
+    use std::io::Write;
+
+    let buffers = [
+        RefOrBox::Borrowed(&b"ab"[..]),
+        RefOrBox::Owned(Box::new(*b"cd")),
+        RefOrBox::Borrowed(&b""[..])
+    ];
+    let io_slices = RefOrBox::as_io_slices(&buffers);
+    assert_eq!(3, io_slices.len());
+    let mut output = Vec::new();
+    let written = output.write_vectored(&io_slices).expect("Write to Vec");
+    assert_eq!(4, written);
+    assert_eq!(b"abcd", &output[..]);
+}
+
+#[test]
+fn split_at_mut() {
+    let owned: RefMutOrBox<[u8]> = RefMutOrBox::Owned(Box::new([1, 2, 3]));
+    let (left, mut right) = owned.split_at_mut(2);
+    right[0] = 4;
+    assert!(matches!(left, RefMutOrBox::Owned(_)));
+    assert_eq!(&[1, 2], &*left);
+    assert_eq!(&[4], &*right);
+
+    let mut data = [1, 2, 3];
+    let (left, right) = RefMutOrBox::from(&mut data[..]).split_at_mut(3);
+    assert!(matches!(right, RefMutOrBox::Borrowed(_)));
+    assert_eq!(3, left.len());
+    assert!(right.is_empty());
+}
+
+//
+// Futures
+//
+
+#[test]
+fn await_wrapped_futures() {
+    let ready = future::ready(5);
+    let borrowed = RefOrOwned::Borrowed(&ready);
+    let owned: RefOrBox<Ready<u8>> = RefOrBox::Owned(Box::new(future::ready(6)));
+    let sum = block_on(async move {
+        borrowed.await + owned.await
+    });
+    assert_eq!(11, sum);
+    assert_eq!(5, block_on(RefOrBox::Borrowed(&ready).into_future()));
+}
+
+#[test]
+fn pinned_futures() {
+    let borrowed = async { 2 };
+    let mut borrowed = Box::pin(borrowed);
+    let futures: Vec<PinRefMutOrBox<dyn Future<Output = u8>>> = vec![
+        PinRefMutOrBox::Borrowed(borrowed.as_mut()),
+        PinRefMutOrBox::Owned(Box::pin(async { 3 })),
+        PinRefMutOrBox::from(Box::new(future::ready(4u8)) as Box<dyn Future<Output = u8>>)
+    ];
+    let sum = block_on(async move {
+        let mut sum = 0;
+        for future in futures {
+            sum += future.await;
+        }
+        sum
+    });
+    assert_eq!(9, sum);
+}
+
+#[test]
+fn pinned_as_mut() {
+    let mut wrapper: PinRefMutOrBox<Vec<u8>> = PinRefMutOrBox::from(Box::new(vec![1]));
+    wrapper.as_mut().get_mut().push(2);
+    assert_eq!(vec![1, 2], *wrapper);
+}
+
+//
+// Non-'static trait objects
+//
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Named<'a> {
+    name: &'a str
+}
+
+impl Describe for Named<'_> {
+    fn describe(&self) -> String {
+        format!("Named {}", self.name)
+    }
+}
+
+struct Describers<'t> {
+    describers: Vec<RefOrBox<'t, dyn Describe + 't>>
+}
+
+/// Holds trait objects which outlive the borrows of the registry itself
+struct Registry<'t, 'a> {
+    entries: Vec<RefOrBox<'t, dyn Describe + 'a>>
+}
+
+fn register<'t, 'a: 't>(registry: &mut Registry<'t, 'a>, named: Named<'a>) {
+    registry.entries.push(RefOrBox::Owned(Box::new(named)));
+}
+
+#[test]
+fn owned_non_static_trait_objects() {
+    let name = String::from("local");
+    let borrowed = Named { name: &name };
+    let describers = Describers {
+        describers: vec![
+            RefOrBox::Borrowed(&borrowed),
+            RefOrBox::Owned(Box::new(Named { name: &name }))
+        ]
+    };
+    for describer in &describers.describers {
+        assert_eq!("Named local", describer.describe());
+    }
+
+    let mut registry = Registry { entries: Vec::new() };
+    register(&mut registry, Named { name: &name });
+    {
+        let short_lived = Named { name: "short" };
+        let mut borrowing_registry = Registry { entries: vec![RefOrBox::Borrowed(&short_lived)] };
+        register(&mut borrowing_registry, Named { name: &name });
+        assert_eq!(2, borrowing_registry.entries.len());
+    }
+    assert_eq!("Named local", registry.entries[0].describe());
+}
+
+#[test]
+fn owned_borrowing_closures() {
+    let threshold = 3;
+    let mut counted = Vec::new();
+    {
+        let mut record: RefMutOrBox<dyn FnMut(u8)> = RefMutOrBox::Owned(Box::new(|value| {
+            if value > threshold {
+                counted.push(value);
+            }
+        }));
+        record(2);
+        record(5);
+    }
+    assert_eq!(vec![5], counted);
+}
+
+//
+// Error downcasting and source chains
+//
+
+#[derive(Debug, PartialEq)]
+struct InnerError;
+
+impl Display for InnerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("inner")
+    }
+}
+
+impl Error for InnerError {}
+
+#[derive(Debug)]
+struct OuterError {
+    inner: InnerError
+}
+
+impl Display for OuterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("outer")
+    }
+}
+
+impl Error for OuterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn error_downcast() {
+    let outer = OuterError { inner: InnerError };
+    let borrowed: RefOrBox<dyn Error + Send + Sync> = RefOrBox::Borrowed(&outer);
+    assert!(borrowed.downcast_ref::<OuterError>().is_some());
+    let borrowed = match borrowed.downcast::<InnerError>() {
+        Err(borrowed) => borrowed,
+        Ok(_) => panic!("Wrong error type")
+    };
+    assert!(matches!(borrowed.downcast::<OuterError>(), Ok(RefOrOwned::Borrowed(_))));
+
+    let owned: RefOrBox<dyn Error> = RefOrBox::Owned(Box::new(InnerError));
+    assert!(owned.downcast_ref::<OuterError>().is_none());
+    assert!(matches!(owned.downcast::<InnerError>(), Ok(RefOrOwned::Owned(InnerError))));
+}
+
+#[test]
+fn error_chain() {
+    let owned: RefOrBox<dyn Error + Send> = RefOrBox::Owned(Box::new(OuterError { inner: InnerError }));
+    let messages: Vec<String> = owned.chain().map(|error| error.to_string()).collect();
+    assert_eq!(vec!["outer", "inner"], messages);
+    assert_eq!(Some(&InnerError), owned.chain().last().and_then(|error| error.downcast_ref()));
+}
+
+//
+// UnwindSafe and RefUnwindSafe
+//
+
+fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
+
+trait SafePlugin: UnwindSafe + RefUnwindSafe {
+    fn run(&self) -> u8;
+}
+
+impl SafePlugin for Bean {
+    fn run(&self) -> u8 {
+        self.data
+    }
+}
+
+#[test]
+fn unwind_safety_propagates() {
+    assert_unwind_safe::<RefOrOwned<Bean>>();
+    assert_unwind_safe::<RefOrBox<str>>();
+    assert_unwind_safe::<RefOrBox<dyn SafePlugin>>();
+    assert_unwind_safe::<RefOrBox<dyn MyTrait + UnwindSafe + RefUnwindSafe>>();
+    assert_unwind_safe::<ArcOrOwned<Bean>>();
+}
+
+#[test]
+fn catch_unwind_with_wrappers() {
+    let bean = Bean::new(3);
+    let borrowed: RefOrBox<dyn SafePlugin> = RefOrBox::Borrowed(&bean);
+    assert_eq!(Ok(3), panic::catch_unwind(move || borrowed.run()).map_err(|_| ()));
+
+    let mut data = vec![1];
+    let mut borrowed_mut = RefMutOrOwned::Borrowed(&mut data);
+    let result = panic::catch_unwind(AssertUnwindSafe(move || {
+        borrowed_mut.push(2);
+        panic!("Plugin failure")
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec![1, 2], data);
+}
+
+//
+// ArcOrOwned, RcOrOwned and shared pointer hand-off
+//
+
+#[test]
+fn arc_or_owned_from_arc() {
+    let unique = Arc::new(Bean::new(1));
+    assert!(matches!(ArcOrOwned::from_arc(unique), ArcOrOwned::Owned(_)));
+
+    let shared = Arc::new(Bean::new(2));
+    let other = Arc::clone(&shared);
+    match ArcOrOwned::from_arc(shared) {
+        ArcOrOwned::Shared(value) => assert!(Arc::ptr_eq(&value, &other)),
+        ArcOrOwned::Owned(_) => panic!("Wrong ArcOrOwned variant")
+    }
+}
+
+#[test]
+fn arc_or_owned_make_mut() {
+    let shared = Arc::new(vec![1]);
+    let mut first: ArcOrOwned<Vec<u8>> = ArcOrOwned::from(Arc::clone(&shared));
+    first.push(2);
+    assert_eq!(vec![1], *shared);
+    assert_eq!(vec![1, 2], *first);
+
+    let mut unique: ArcOrOwned<Vec<u8>> = ArcOrOwned::from(Arc::new(vec![3]));
+    unique.push(4);
+    assert_eq!(vec![3, 4], unique.into_owned());
+}
+
+#[test]
+fn arc_or_owned_std_traits() {
+    let generated = ArcOrOwned::Shared(Arc::new(Bean::default()));
+    let incremented = ArcOrOwned::Owned(Bean::new(generated.data + 1));
+
+    let _fmt = format!("Is: {}", &generated);
+    assert_eq!(eval_hash(&Bean::new(generated.data)), eval_hash(&generated));
+    assert!(eval_eq(&generated, &ArcOrOwned::Owned(Bean::new(generated.data))));
+    assert!(!eval_partial_eq(&generated, &incremented));
+    assert_eq!(Ordering::Less, eval_ord(&generated, &incremented));
+}
+
+#[test]
+fn rc_or_owned_from_rc() {
+    let unique = Rc::new(Bean::new(1));
+    assert!(matches!(RcOrOwned::from_rc(unique), RcOrOwned::Owned(_)));
+
+    let shared = Rc::new(Bean::new(2));
+    let other = Rc::clone(&shared);
+    match RcOrOwned::from_rc(shared) {
+        RcOrOwned::Shared(value) => assert!(Rc::ptr_eq(&value, &other)),
+        RcOrOwned::Owned(_) => panic!("Wrong RcOrOwned variant")
+    }
+}
+
+#[test]
+fn rc_or_owned_make_mut() {
+    let shared = Rc::new(vec![1]);
+    let mut first: RcOrOwned<Vec<u8>> = RcOrOwned::from(Rc::clone(&shared));
+    first.push(2);
+    assert_eq!(vec![1], *shared);
+    assert_eq!(vec![1, 2], *first);
+    assert_eq!(1, Rc::strong_count(&shared));
+
+    let mut unique: RcOrOwned<Vec<u8>> = RcOrOwned::from(Rc::new(vec![3]));
+    unique.push(4);
+    assert_eq!(vec![3, 4], unique.into_owned());
+}
+
+#[test]
+fn shared_or_owned_conversions() {
+    type Data = Vec<u8>;
+
+    let shared = Arc::new(vec![1]);
+    let cloned = RefOrOwned::<Data>::from(ArcOrOwned::<Data>::from(Arc::clone(&shared)));
+    assert!(matches!(cloned, RefOrOwned::Owned(_)));
+    assert_eq!(1, Arc::strong_count(&shared));
+
+    let moved = RcOrOwned::<Data>::from(ArcOrOwned::<Data>::from(shared));
+    let boxed = RefOrBox::<Data>::from(moved);
+    assert_eq!(vec![1], *boxed);
+
+    let data = vec![2];
+    let from_borrowed = ArcOrOwned::<Data>::from(RefOrOwned::Borrowed(&data));
+    let from_box = RcOrOwned::<Data>::from(RefOrBox::Owned(Box::new(vec![2])));
+    assert!(matches!(from_borrowed, ArcOrOwned::Owned(_)));
+    assert!(matches!(from_box, RcOrOwned::Owned(_)));
+    assert_eq!(*from_borrowed, *ArcOrOwned::<Data>::from(from_box));
+}
+
+#[test]
+fn ref_or_owned_try_from_arc() {
+    let unique = Arc::new(Bean::new(1));
+    assert!(matches!(RefOrOwned::<Bean>::try_from(unique), Ok(RefOrOwned::Owned(_))));
+
+    let shared = Arc::new(Bean::new(2));
+    let _other = Arc::clone(&shared);
+    let shared = match RefOrOwned::try_from_arc(shared) {
+        Err(shared) => shared,
+        Ok(_) => panic!("Arc is shared")
+    };
+    assert_eq!(2, Arc::strong_count(&shared));
+
+    let cloned: RefOrOwned<Vec<u8>> = RefOrOwned::from_arc(Arc::new(vec![1]));
+    assert!(matches!(cloned, RefOrOwned::Owned(_)));
+}
+
+//
+// heapless-backed StrOrHeapless and SliceOrHeaplessVec
+//
+
+#[test]
+#[cfg(feature = "heapless")]
+fn str_or_heapless_into_owned() {
+    let borrowed: StrOrHeapless<8> = StrOrHeapless::from("borrow");
+    assert_eq!("borrow", borrowed.into_owned().unwrap().as_str());
+
+    let owned: StrOrHeapless<8> = StrOrHeapless::from(heapless::String::try_from("own").unwrap());
+    assert_eq!("own", owned.into_owned().unwrap().as_str());
+
+    let overflowing: StrOrHeapless<2> = StrOrHeapless::from("overflow");
+    assert_eq!(Err("overflow"), overflowing.into_owned());
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn str_or_heapless_std_traits() {
+    let borrowed: StrOrHeapless<8> = StrOrHeapless::Borrowed("abc");
+    let owned: StrOrHeapless<4> = StrOrHeapless::Owned(heapless::String::try_from("abd").unwrap());
+
+    assert_eq!("Is: abc", format!("Is: {}", &borrowed));
+    assert_eq!(eval_hash(&"abc"), eval_hash(&borrowed));
+    assert!(borrowed != owned);
+    assert_eq!(Some(Ordering::Less), borrowed.partial_cmp(&owned));
+    assert_eq!(Ordering::Equal, eval_ord(&borrowed, &StrOrHeapless::Borrowed("abc")));
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn slice_or_heapless_vec_into_owned() {
+    let data = [4, 5, 6];
+    let borrowed: SliceOrHeaplessVec<u8, 3> = SliceOrHeaplessVec::from(&data[..]);
+    assert_eq!(15, borrowed.iter().sum::<u8>());
+    assert_eq!(&data, borrowed.into_owned().unwrap().as_slice());
+
+    let overflowing: SliceOrHeaplessVec<u8, 2> = SliceOrHeaplessVec::from(&data[..]);
+    assert_eq!(Err(&data[..]), overflowing.into_owned());
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn slice_or_heapless_vec_std_traits() {
+    let data = [1, 2];
+    let borrowed: SliceOrHeaplessVec<u8, 2> = SliceOrHeaplessVec::Borrowed(&data);
+    let owned: SliceOrHeaplessVec<u8, 4> = SliceOrHeaplessVec::Owned(heapless::Vec::from_slice(&[1, 3]).unwrap());
+
+    assert_eq!(eval_hash(&&data[..]), eval_hash(&borrowed));
+    assert!(borrowed != owned);
+    assert_eq!(Some(Ordering::Less), borrowed.partial_cmp(&owned));
+    assert!(SliceOrHeaplessVec::<u8, 2>::default().is_empty());
+}
+
+//
+// memmap2-backed MmapOrVec
+//
+
+#[cfg(feature = "memmap2")]
+fn mapped_bytes(bytes: &[u8]) -> memmap2::Mmap {
+    let mut mapped = memmap2::MmapMut::map_anon(bytes.len()).expect("Anonymous mapping failed");
+    mapped.copy_from_slice(bytes);
+    mapped.make_read_only().expect("Mapping could not be made read-only")
+}
+
+#[test]
+#[cfg(feature = "memmap2")]
+fn mmap_or_vec_deref_and_into_owned() {
+    let mapped = MmapOrVec::from(mapped_bytes(&[1, 2, 3]));
+    let owned = MmapOrVec::from(vec![1, 2, 3]);
+    assert_eq!(&[1, 2, 3], &*mapped);
+    assert!(eval_eq(&mapped, &owned));
+    assert_eq!(eval_hash(&mapped), eval_hash(&owned));
+    assert_eq!(vec![1, 2, 3], mapped.into_owned());
+}
+
+#[test]
+#[cfg(feature = "memmap2")]
+fn mmap_or_vec_to_mut() {
+    let mut data = MmapOrVec::from(mapped_bytes(&[4, 5]));
+    data.to_mut().push(6);
+    match data {
+        MmapOrVec::Mapped(_) => panic!("Wrong MmapOrVec variant"),
+        MmapOrVec::Owned(value) => assert_eq!(vec![4, 5, 6], value)
+    }
+}
+
+//
+// bytemuck-based casting
+//
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn cast_borrowed_aligned_stays_borrowed() {
+    let words: [u32; 2] = [1, 2];
+    let bytes: &[u8] = bytemuck::cast_slice(&words);
+    let bytes = RefOrBox::Borrowed(bytes);
+    match bytes.cast::<u32>() {
+        Ok(RefOrBox::Borrowed(cast_words)) => assert_eq!(&words, cast_words),
+        _ => panic!("Wrong RefOrBox variant")
+    }
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn cast_borrowed_misaligned_is_copied() {
+    let words: [u32; 3] = [0, 7, 9];
+    let bytes: &[u8] = bytemuck::cast_slice(&words);
+    let misaligned = RefOrBox::Borrowed(&bytes[1..5]);
+    let cast = misaligned.cast::<u32>().unwrap();
+    assert!(matches!(cast, RefOrBox::Owned(_)));
+    assert_eq!(&[u32::from_ne_bytes([bytes[1], bytes[2], bytes[3], bytes[4]])], &*cast);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn cast_owned() {
+    let signed: RefOrBox<[i32]> = RefOrBox::Owned(Box::new([-1, 1]));
+    let unsigned = signed.cast::<u32>().unwrap();
+    assert_eq!(&[u32::MAX, 1], &*unsigned);
+
+    let bytes: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([0; 8]));
+    let words = bytes.cast::<u64>().unwrap();
+    assert_eq!(&[0], &*words);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn cast_with_slop_fails() {
+    let bytes: RefOrBox<[u8]> = RefOrBox::Owned(Box::new([0; 3]));
+    assert_eq!(Some(bytemuck::PodCastError::OutputSliceWouldHaveSlop), bytes.cast::<u16>().err());
+    let unit: RefOrBox<[()]> = RefOrBox::Owned(Box::new([(); 2]));
+    assert_eq!(Some(bytemuck::PodCastError::SizeMismatch), unit.cast::<u8>().err());
+}
+
+//
+// defmt formatting
+//
+
+#[cfg(feature = "defmt")]
+fn assert_defmt_format<T: ?Sized + defmt::Format>() {}
+
+#[test]
+#[cfg(feature = "defmt")]
+fn defmt_format_forwarding() {
+    assert_defmt_format::<RefOrOwned<u8>>();
+    assert_defmt_format::<RefMutOrOwned<u8>>();
+    assert_defmt_format::<RefOrBox<str>>();
+    assert_defmt_format::<RefMutOrBox<[u16]>>();
+    assert_defmt_format::<ArcOrOwned<u8>>();
+    assert_defmt_format::<RcOrOwned<u8>>();
+}
+
+//
+// serde support
+//
+
+#[test]
+#[cfg(feature = "rc")]
+fn serde_shared_or_owned() {
+    let shared = Arc::new(vec![1, 2]);
+    let wrapper: ArcOrOwned<Vec<u8>> = ArcOrOwned::from(Arc::clone(&shared));
+    assert_eq!("[1,2]", serde_json::to_string(&wrapper).unwrap());
+    assert_eq!("[3]", serde_json::to_string(&RcOrOwned::Owned(vec![3])).unwrap());
+
+    let mut deserialized: ArcOrOwned<Vec<u8>> = serde_json::from_str("[1,2]").unwrap();
+    match &deserialized {
+        ArcOrOwned::Shared(arc) => assert_eq!(1, Arc::strong_count(arc)),
+        ArcOrOwned::Owned(_) => panic!("Wrong ArcOrOwned variant")
+    }
+    deserialized.push(3);
+    assert_eq!(vec![1, 2, 3], deserialized.into_owned());
+
+    let deserialized: RcOrOwned<String> = serde_json::from_str("\"text\"").unwrap();
+    assert!(matches!(deserialized, RcOrOwned::Shared(_)));
+
+    let repeated: [ArcOrOwned<Vec<u8>>; 2] = [ArcOrOwned::Shared(Arc::clone(&shared)), ArcOrOwned::Shared(shared)];
+    let json = serde_json::to_string(&repeated).unwrap();
+    let deserialized: Vec<ArcOrOwned<Vec<u8>>> = serde_json::from_str(&json).unwrap();
+    assert!(!std::ptr::eq(&*deserialized[0], &*deserialized[1]));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_serialize_passthrough() {
+    let text = String::from("text");
+    let mut numbers = vec![1, 2];
+    assert_eq!("\"text\"", serde_json::to_string(&RefOrOwned::Borrowed(&text)).unwrap());
+    assert_eq!("\"text\"", serde_json::to_string(&RefOrOwned::Owned(text.clone())).unwrap());
+    assert_eq!("[1,2]", serde_json::to_string(&RefMutOrOwned::Borrowed(&mut numbers)).unwrap());
+
+    let borrowed: RefOrBox<str> = RefOrBox::Borrowed("text");
+    let owned: RefMutOrBox<[u8]> = RefMutOrBox::Owned(Box::new([3]));
+    assert_eq!("\"text\"", serde_json::to_string(&borrowed).unwrap());
+    assert_eq!("[3]", serde_json::to_string(&owned).unwrap());
+    assert_eq!("[1,2]", serde_json::to_string(&RefMutOrBox::from(&mut numbers[..])).unwrap());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_borrow_from_input() {
+    let input = String::from("\"plain\"");
+    let plain: RefOrBox<str> = serde_json::from_str(&input).unwrap();
+    assert!(plain.is_borrowed());
+    assert!(std::ptr::eq(&input[1..6], &*plain));
+
+    let escaped: RefOrBox<str> = serde_json::from_str("\"esc\\\"aped\"").unwrap();
+    assert!(escaped.is_owned());
+    assert_eq!("esc\"aped", &*escaped);
+
+    let bytes: RefOrBox<[u8]> = serde_json::from_str("[1,2,3]").unwrap();
+    assert!(bytes.is_owned());
+    assert_eq!([1, 2, 3], *bytes);
+}
+
+//
+// schemars support
+//
+
+#[test]
+#[cfg(feature = "schemars")]
+fn schemars_forwards_to_value() {
+    let expected = schemars::schema_for!(Vec<String>);
+    assert_eq!(expected, schemars::schema_for!(RefOrOwned<Vec<String>>));
+    assert_eq!(expected, schemars::schema_for!(RefMutOrOwned<Vec<String>>));
+    assert_eq!(expected, schemars::schema_for!(RefOrBox<Vec<String>>));
+    assert_eq!(expected, schemars::schema_for!(ArcOrOwned<Vec<String>>));
+
+    let expected = schemars::schema_for!(str);
+    assert_eq!(expected, schemars::schema_for!(RefOrBox<str>));
+    assert_eq!(expected, schemars::schema_for!(RefMutOrBox<str>));
+    assert_eq!(schemars::schema_for!(u8), schemars::schema_for!(RcOrOwned<u8>));
+}
+
+//
+// arbitrary support
+//
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn arbitrary_owned() {
+    use arbitrary::{Arbitrary, Unstructured};
+    let mut input = Unstructured::new(&[1, 0, 0, 0, 2, 0]);
+    let first = RefOrOwned::<u32>::arbitrary(&mut input).unwrap();
+    let second = RefMutOrOwned::<u16>::arbitrary(&mut input).unwrap();
+    assert_eq!(RefOrOwned::Owned(1), first);
+    assert!(matches!(second, RefMutOrOwned::Owned(2)));
+    assert_eq!((4, Some(4)), RefOrOwned::<u32>::size_hint(0));
+}
+
+//
+// proptest support
+//
+
+#[cfg(feature = "proptest")]
+mod proptest_support {
+    use crate::ref_or_owned::*;
+    use proptest::prelude::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+    use std::fmt::Debug;
+
+    #[test]
+    fn both_variants_generated() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = ref_or_owned_strategy(any::<u32>());
+        let kinds: Vec<Kind> = (0..64)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current().with_ref_or_owned(|value| value.kind()))
+            .collect();
+        assert!(kinds.contains(&Kind::Borrowed));
+        assert!(kinds.contains(&Kind::Owned));
+    }
+
+    #[test]
+    fn shrink_to_owned_and_payload() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = ref_or_owned_strategy(1..100_u32).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        let simplest = tree.current();
+        assert_eq!(Kind::Owned, simplest.kind());
+        assert_eq!(1, simplest.with_ref_mut_or_owned(|value| *value));
+    }
+
+    proptest! {
+        #[test]
+        fn boxed_trait_objects(generated in ref_or_box_strategy(any::<i8>().prop_map(|n| Box::new(n) as Box<dyn Debug>))) {
+            let kind = generated.kind();
+            let formatted = generated.with_ref_or_box(|value| {
+                assert_eq!(kind, value.kind());
+                format!("{:?}", value)
+            });
+            prop_assert!(!formatted.is_empty());
+        }
+
+        #[test]
+        fn mutable_boxes(generated in ref_or_box_strategy(any::<u8>().prop_map(Box::new))) {
+            let expected = generated.payload().wrapping_add(1);
+            let incremented = generated.with_ref_mut_or_box(|mut value| {
+                *value = value.wrapping_add(1);
+                *value
+            });
+            prop_assert_eq!(expected, incremented);
+        }
+    }
+}
+
+//
+// quickcheck support
+//
+
+#[test]
+#[cfg(feature = "quickcheck")]
+fn quickcheck_generation() {
+    use quickcheck::{Arbitrary, Gen};
+    let mut gen = Gen::new(10);
+    let values: Vec<RefOrOwned<u32>> = (0..64).map(|_| RefOrOwned::arbitrary(&mut gen)).collect();
+    assert!(values.iter().all(RefOrOwned::is_owned));
+    assert!(RefOrOwned::Owned(5_u32).shrink().all(|value| value.is_owned() && *value < 5));
+    let shared: Vec<ArcOrOwned<u8>> = (0..64).map(|_| ArcOrOwned::arbitrary(&mut gen)).collect();
+    assert!(shared.iter().any(|value| matches!(value, ArcOrOwned::Shared(_))));
+    assert!(shared.iter().any(|value| matches!(value, ArcOrOwned::Owned(_))));
+}
+
+#[test]
+#[cfg(feature = "quickcheck")]
+fn quickcheck_shrink_to_owned() {
+    use quickcheck::Arbitrary;
+    let borrowed = RefOrOwned::Borrowed(&4_u32);
+    let shrunk: Vec<RefOrOwned<u32>> = borrowed.shrink().collect();
+    assert!(shrunk.iter().all(RefOrOwned::is_owned));
+    assert_eq!(RefOrOwned::Owned(4), shrunk[0]);
+    assert_eq!(4_u32.shrink().count() + 1, shrunk.len());
+
+    let owned: RcOrOwned<u32> = RcOrOwned::Owned(4);
+    let shrunk: Vec<u32> = owned.shrink().map(RcOrOwned::into_owned).collect();
+    assert_eq!(4_u32.shrink().collect::<Vec<_>>(), shrunk);
+}
+
+#[test]
+#[cfg(feature = "quickcheck")]
+fn quickcheck_property() {
+    fn length_matches(value: RefOrOwned<'static, String>) -> bool {
+        value.len() == value.as_borrowed().len()
+    }
+    quickcheck::quickcheck(length_matches as fn(RefOrOwned<'static, String>) -> bool);
+}
+
+#[test]
+fn compare_across_families() {
+    let shared = 5;
+    let mut mutable = 6;
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    let boxed: RefOrBox<u32> = RefOrBox::Owned(Box::new(5));
+    let mutable_owned: RefMutOrOwned<u32> = RefMutOrOwned::Borrowed(&mut mutable);
+    let mutable_boxed: RefMutOrBox<u32> = RefMutOrBox::Owned(Box::new(6));
+
+    assert_eq!(borrowed, boxed);
+    assert_eq!(boxed, borrowed);
+    assert_eq!(mutable_owned, mutable_boxed);
+    assert!(borrowed < mutable_owned);
+    assert!(boxed < mutable_boxed);
+    assert!(mutable_boxed > borrowed);
+}
+
+//
+// Comparisons against bare values
+//
+
+#[test]
+fn compare_with_bare_values() {
+    let shared = 5;
+    assert_eq!(RefOrOwned::Borrowed(&shared), 5);
+    assert_eq!(RefOrOwned::Owned(5), &shared);
+    assert!(RefOrOwned::Owned(4) < 5);
+
+    let mut text = String::from("text");
+    assert_eq!(RefMutOrOwned::Borrowed(&mut text), String::from("text"));
+    assert_eq!(RefMutOrOwned::Owned(1.5), 1.5);
+
+    let boxed: RefOrBox<str> = RefOrBox::Owned(Box::from("text"));
+    assert_eq!(boxed, "text");
+    assert!(boxed < "zzz");
+    let mut array = [1, 2];
+    let slice: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    assert_eq!(slice, &[1, 2][..]);
+
+    assert_eq!(RefOrBox::Owned(Box::new(5_u32)), 5);
+    assert!(RefMutOrBox::Borrowed(&mut text) > String::from("a"));
+    let bean = Bean::new(1);
+    assert!(RefOrBox::Owned(Box::new(Bean::new(1))) == &bean);
+}
+
+//
+// Variant inspection
+//
+
+#[test]
+fn kind_inspection() {
+    let mut value = Bean::new(1);
+    let borrowed = RefOrOwned::Borrowed(&value);
+    assert_eq!(Kind::Borrowed, borrowed.kind());
+    assert!(borrowed.is_borrowed());
+    assert!(!borrowed.is_owned());
+
+    let owned: RefMutOrOwned<Bean> = RefMutOrOwned::Owned(Bean::new(2));
+    assert_eq!(Kind::Owned, owned.kind());
+    assert!(owned.is_owned());
+
+    let boxed: RefOrBox<dyn MyTrait> = RefOrBox::Owned(Box::new(Implementor::default()));
+    assert!(boxed.is_owned());
+    let mut_borrowed: RefMutOrBox<Bean> = RefMutOrBox::Borrowed(&mut value);
+    assert!(mut_borrowed.is_borrowed());
+}
+
+//
+// Clone on write
+//
+
+#[test]
+fn ref_or_owned_to_mut() {
+    let original = ClonableStruct::default();
+    let mut wrapper = RefOrOwned::Borrowed(&original);
+    wrapper.to_mut();
+    assert!(wrapper.is_owned());
+
+    let mut owned = RefOrOwned::Owned(vec![1]);
+    owned.to_mut().push(2);
+    owned.to_mut().push(3);
+    assert_eq!(vec![1, 2, 3], *owned);
+}
+
+#[test]
+#[cfg(feature = "trait-clone")]
+fn ref_or_box_to_mut() {
+    let clonable = ClonableStruct::default();
+    let mut wrapper: RefOrBox<dyn CloneTrait> = RefOrBox::Borrowed(&clonable);
+    let _: &mut dyn CloneTrait = wrapper.to_mut();
+    assert!(wrapper.is_owned());
+
+    let mut slice: RefOrBox<[u8]> = RefOrBox::Borrowed(&[1, 2]);
+    slice.to_mut()[0] = 3;
+    assert_eq!([3, 2], *slice);
+}
+
+//
+// Reborrowing
+//
+
+#[test]
+fn reborrow_mutable_wrappers() {
+    fn increment(mut bean: RefMutOrOwned<Bean>) {
+        bean.data += 1;
+    }
+
+    let mut bean: RefMutOrOwned<Bean> = RefMutOrOwned::Owned(Bean::new(1));
+    increment(bean.reborrow());
+    increment(bean.reborrow());
+    assert!(bean.is_owned());
+    assert_eq!(3, bean.data);
+
+    let mut implementor = Implementor::default();
+    let mut wrapper: RefMutOrBox<dyn MyTrait> = RefMutOrBox::Borrowed(&mut implementor);
+    wrapper.reborrow().do_mutable();
+    wrapper.reborrow().do_mutable();
+    assert!(wrapper.reborrow().is_borrowed());
+    drop(wrapper);
+    assert_eq!(2, implementor.mut_calls());
+}
+
+#[test]
+fn as_borrowed_views() {
+    let owned = RefOrOwned::Owned(Bean::new(1));
+    let views = [owned.as_borrowed(), owned.as_borrowed()];
+    assert!(views.iter().all(|view| view.is_borrowed() && std::ptr::eq(&*owned, &**view)));
+
+    let mut bean = Bean::new(2);
+    let mutable = RefMutOrOwned::Borrowed(&mut bean);
+    assert_eq!(2, mutable.as_borrowed().data);
+
+    let boxed: RefOrBox<dyn MyTrait> = RefOrBox::Owned(Box::new(Implementor::default()));
+    assert!(boxed.as_borrowed().is_borrowed());
+    let mutable: RefMutOrBox<str> = RefMutOrBox::Owned(Box::from("text"));
+    assert_eq!(RefOrBox::Borrowed("text"), mutable.as_borrowed());
+}
+
+#[test]
+fn take_and_replace() {
+    let shared = vec![1];
+    let mut state = RefOrOwned::Borrowed(&shared);
+    let previous = state.replace_wrapper(vec![2]);
+    assert!(previous.is_borrowed());
+    assert_eq!(vec![2], *state);
+    assert_eq!(vec![2], *state.take_wrapper());
+    assert!(state.is_owned() && state.is_empty());
+
+    let mut bean = Bean::new(3);
+    let mut state = RefMutOrOwned::Borrowed(&mut bean);
+    assert!(state.replace_wrapper(Bean::new(4)).is_borrowed());
+    let previous = state.take_wrapper();
+    assert_eq!(4, previous.data);
+    assert!(state.is_owned());
+}
+
+#[test]
+fn take_and_replace_reach_wrapped_value() {
+    let mut slot = Some(5);
+    let mut state = RefMutOrOwned::Borrowed(&mut slot);
+    assert_eq!(Some(5), state.take());
+    assert!(state.is_borrowed());
+    assert_eq!(None, state.replace(6));
+    assert_eq!(Some(6), slot);
+}
+
+#[test]
+fn promotion_without_clone() {
+    let shared = Bean::new(1);
+    let copied = RefOrOwned::Borrowed(&shared).owned_or_else(|bean| Bean::new(bean.data + 1));
+    assert_eq!(2, copied.data);
+    assert_eq!(5, RefOrOwned::Owned(Bean::new(5)).owned_or_else(|_| Bean::new(0)).data);
+
+    let mut wrapper = RefOrOwned::Borrowed(&shared);
+    wrapper.get_or_insert_owned(|bean| Bean::new(bean.data)).data = 3;
+    wrapper.get_or_insert_owned(|_| Bean::new(0)).data += 1;
+    assert!(wrapper.is_owned());
+    assert_eq!(4, wrapper.data);
+
+    let mut bean = Bean::new(6);
+    let mut wrapper = RefMutOrOwned::Borrowed(&mut bean);
+    wrapper.get_or_insert_owned(|bean| Bean::new(bean.data)).data = 7;
+    assert_eq!(7, wrapper.data);
+    assert_eq!(6, bean.data);
+}
+
+#[test]
+fn try_into_owned_without_clone() {
+    let shared = Bean::new(1);
+    assert!(RefOrOwned::Borrowed(&shared).try_into_owned().is_err());
+    assert_eq!(2, RefOrOwned::Owned(Bean::new(2)).try_into_owned().ok().unwrap().data);
+
+    let mut bean = Bean::new(3);
+    assert!(RefMutOrOwned::Borrowed(&mut bean).try_into_owned().is_err());
+    assert_eq!(4, RefMutOrOwned::Owned(Bean::new(4)).try_into_owned().ok().unwrap().data);
+
+    let text: RefOrBox<str> = RefOrBox::Borrowed("text");
+    assert_eq!(Err("text"), text.try_into_owned());
+    let text: RefMutOrBox<str> = RefMutOrBox::Owned(Box::from("text"));
+    assert_eq!(Ok(Box::from("text")), text.try_into_owned());
+}
+
+#[test]
+fn option_accessors() {
+    let shared = Bean::new(1);
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    assert!(std::ptr::eq(&shared, borrowed.borrowed().unwrap()));
+    assert!(borrowed.owned_ref().is_none());
+    assert!(borrowed.into_owned_opt().is_none());
+
+    let owned: RefMutOrOwned<Bean> = RefMutOrOwned::Owned(Bean::new(2));
+    assert!(owned.borrowed().is_none());
+    assert_eq!(2, owned.owned_ref().unwrap().data);
+    assert_eq!(2, owned.into_owned_opt().unwrap().data);
+
+    let boxed: RefOrBox<str> = RefOrBox::Owned(Box::from("owned"));
+    assert_eq!(None, boxed.borrowed());
+    assert_eq!(Some("owned"), boxed.owned_ref());
+    assert_eq!(Some(Box::from("owned")), boxed.into_owned_opt());
+    let mut array = [1];
+    let mutable: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    assert_eq!(Some(&[1][..]), mutable.borrowed());
+    assert!(mutable.into_owned_opt().is_none());
+}
+
+#[test]
+fn as_deref_projection() {
+    let owned: RefOrOwned<String> = RefOrOwned::Owned(String::from("text"));
+    assert_eq!(RefOrBox::Borrowed("text"), owned.as_deref());
+    let shared = vec![1, 2];
+    let borrowed = RefOrOwned::Borrowed(&shared);
+    let slice: RefOrBox<[u8]> = borrowed.as_deref();
+    assert!(std::ptr::eq(shared.as_slice(), &*slice));
+
+    let mut list = vec![3, 4];
+    let mut wrapper = RefMutOrOwned::Borrowed(&mut list);
+    wrapper.as_deref_mut()[0] = 5;
+    assert_eq!([5, 4], *wrapper.as_deref());
+}
+
+//
+// Conversions between families
+//
+
+#[test]
+fn ref_or_owned_and_ref_or_box() {
+    let bean = Bean::new(1);
+    let boxed: RefOrBox<Bean> = RefOrOwned::Borrowed(&bean).into();
+    assert!(matches!(boxed, RefOrBox::Borrowed(_)));
+    let owned: RefOrOwned<Bean> = RefOrBox::Owned(Box::new(Bean::new(2))).into();
+    assert!(matches!(owned, RefOrOwned::Owned(Bean { data: 2 })));
+    assert!(owned.into_ref_or_box().into_ref_or_owned().is_owned());
+
+    let mut bean = Bean::new(3);
+    let mut boxed: RefMutOrBox<Bean> = RefMutOrOwned::Borrowed(&mut bean).into();
+    boxed.data = 4;
+    let owned: RefMutOrOwned<Bean> = boxed.into();
+    assert!(owned.is_borrowed());
+    assert_eq!(4, bean.data);
+    assert!(RefMutOrOwned::Owned(Bean::new(5)).into_ref_mut_or_box().into_ref_mut_or_owned().is_owned());
+}
+
+#[test]
+fn downgrade_mutable_wrappers() {
+    let mut bean = Bean::new(1);
+    let shared: RefOrOwned<Bean> = RefMutOrOwned::Borrowed(&mut bean).into();
+    assert!(shared.is_borrowed());
+    assert!(RefMutOrOwned::Owned(Bean::new(2)).into_shared().is_owned());
+
+    let mut implementor = Implementor::default();
+    let shared: RefOrBox<dyn MyTrait> = RefMutOrBox::Borrowed(&mut implementor as &mut dyn MyTrait).into();
+    assert!(shared.is_borrowed());
+    let owned: RefMutOrBox<str> = RefMutOrBox::Owned(Box::from("owned"));
+    assert_eq!(RefOrBox::Borrowed("owned"), owned.into_shared());
+}
+
+//
+// Cow interoperability
+//
+
+#[test]
+fn cow_conversions() {
+    use std::borrow::Cow;
+
+    let shared = 1;
+    let wrapper: RefOrOwned<u32> = Cow::Borrowed(&shared).into();
+    assert!(wrapper.is_borrowed());
+    let cow: Cow<u32> = RefOrOwned::Owned(2).into();
+    assert!(matches!(cow, Cow::Owned(_)));
+
+    let wrapper: RefOrBox<str> = Cow::<str>::Owned(String::from("owned")).into();
+    assert_eq!("owned", &*wrapper);
+    assert!(wrapper.is_owned());
+    let cow: Cow<[u8]> = RefOrBox::Borrowed(&[3][..]).into();
+    assert!(matches!(cow, Cow::Borrowed(&[3])));
+    let cow: Cow<str> = RefOrBox::<str>::Owned(Box::from("text")).into();
+    assert_eq!(Cow::<str>::Owned(String::from("text")), cow);
+}
+
+#[test]
+fn cow_equality() {
+    use std::borrow::Cow;
+
+    let shared = 1;
+    assert_eq!(RefOrOwned::Borrowed(&shared), Cow::<u32>::Owned(1));
+    assert_eq!(Cow::Borrowed(&shared), RefOrOwned::Owned(1));
+    let boxed: RefOrBox<str> = RefOrBox::Owned(Box::from("text"));
+    assert_eq!(boxed, Cow::Borrowed("text"));
+    assert_ne!(Cow::<str>::Owned(String::from("other")), boxed);
+}
+
+#[test]
+#[cfg(feature = "beef")]
+fn beef_conversions() {
+    let wrapper: RefOrBox<str> = beef::Cow::borrowed("borrowed").into();
+    assert!(matches!(wrapper, RefOrBox::Borrowed("borrowed")));
+    let cow: beef::Cow<[u8]> = RefOrBox::<[u8]>::Owned(Box::new([3])).into();
+    assert!(cow.is_owned());
+    assert_eq!([3], *cow);
+}
+
+#[test]
+#[cfg(all(feature = "beef", target_pointer_width = "64"))]
+fn beef_lean_conversions() {
+    let wrapper: RefOrBox<[u8]> = beef::lean::Cow::<[u8]>::owned(vec![1, 2]).into();
+    assert!(wrapper.is_owned());
+    assert_eq!([1, 2], *wrapper);
+
+    let cow: beef::lean::Cow<str> = RefOrBox::Borrowed("borrowed").into();
+    assert!(cow.is_borrowed());
+}
+
+//
+// Mapping
+//
+
+#[test]
+fn map_preserves_variant() {
+    let bean = Bean::new(4);
+    let borrowed = RefOrOwned::Borrowed(&bean).map_variant(|bean| &bean.data, |bean| bean.data);
+    assert!(matches!(borrowed, RefOrOwned::Borrowed(&4)));
+    let owned = RefOrOwned::Owned(Bean::new(5)).map_variant(|bean| &bean.data, |bean| bean.data);
+    assert!(matches!(owned, RefOrOwned::Owned(5)));
+
+    let mut bean = Bean::new(6);
+    let mut mapped = RefMutOrOwned::Borrowed(&mut bean).map_variant(|bean| &mut bean.data, |bean| bean.data);
+    *mapped += 1;
+    assert_eq!(7, bean.data);
+
+    let text: RefOrBox<str> = RefOrBox::Owned(Box::from("text"));
+    let bytes = text.map_variant(str::as_bytes, |text| text.into_boxed_bytes());
+    assert!(matches!(bytes, RefOrBox::Owned(_)));
+    assert_eq!(b"text", &*bytes);
+
+    let mut array = [1, 2, 3];
+    let slice: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    let mut tail = slice.map_variant(|slice| &mut slice[1..], |slice| slice[1..].into());
+    tail[0] = 5;
+    assert_eq!([1, 5, 3], array);
+}
+
+#[test]
+fn try_map_preserves_variant() {
+    let text = String::from("12");
+    let parsed: Result<RefOrOwned<u8>, ()> = RefOrOwned::Borrowed(&text).try_map_variant(|_| Err(()), |_| Ok(0));
+    assert!(parsed.is_err());
+    let parsed = RefOrOwned::Owned(text).try_map_variant(|_| Err(()), |text| text.parse::<u8>().map_err(|_| ()));
+    assert!(matches!(parsed, Ok(RefOrOwned::Owned(12))));
+
+    let bytes: RefOrBox<[u8]> = RefOrBox::Borrowed(b"text");
+    let text = bytes.try_map_variant(std::str::from_utf8, |bytes| String::from_utf8(bytes.into()).map(String::into_boxed_str).map_err(|e| e.utf8_error()));
+    assert!(matches!(text, Ok(RefOrBox::Borrowed("text"))));
+
+    let mut array = [1, 2];
+    let slice: RefMutOrBox<[u8]> = RefMutOrBox::Borrowed(&mut array);
+    assert!(slice.try_map_variant(|slice| slice.get_mut(5..).ok_or(()), |_| Err(())).is_err());
+    let mut bean = Bean::new(1);
+    let mapped = RefMutOrOwned::Borrowed(&mut bean).try_map_variant::<u8, (), _, _>(|bean| Ok(&mut bean.data), |bean| Ok(bean.data));
+    assert!(mapped.unwrap().is_borrowed());
+}
+
+//
+// Web framework responses
+//
+
+#[test]
+#[cfg(feature = "axum")]
+fn axum_into_response() {
+    use axum_core::response::IntoResponse;
+
+    let borrowed: RefOrBox<'static, str> = RefOrBox::Borrowed("text");
+    let owned: RefOrBox<'static, [u8]> = RefOrBox::Owned(Box::new([1, 2]));
+    assert_eq!("text/plain; charset=utf-8", borrowed.into_response().headers()["content-type"]);
+    assert_eq!("application/octet-stream", owned.into_response().headers()["content-type"]);
+}
+
+#[test]
+#[cfg(feature = "actix")]
+fn actix_responder() {
+    use actix_web::Responder;
+    use actix_web::test::TestRequest;
+
+    let request = TestRequest::default().to_http_request();
+    let borrowed: RefOrBox<'static, [u8]> = RefOrBox::Borrowed(&[1, 2]);
+    let owned: RefOrBox<'static, str> = RefOrBox::Owned(Box::from("text"));
+    assert_eq!("application/octet-stream", borrowed.respond_to(&request).headers().get("content-type").unwrap());
+    assert_eq!("text/plain; charset=utf-8", owned.respond_to(&request).headers().get("content-type").unwrap());
+}
+
+//
+// Drop hooks
+//
+
+#[test]
+fn on_drop_owned() {
+    let finalized = std::cell::RefCell::new(Vec::new());
+    let shared = Bean::new(1);
+
+    let borrowed = OnDropOwned::new(RefOrOwned::Borrowed(&shared), |bean: Bean| finalized.borrow_mut().push(bean.data));
+    let owned = OnDropOwned::new(RefOrOwned::Owned(Bean::new(2)), |bean: Bean| finalized.borrow_mut().push(bean.data));
+    assert_eq!(1, borrowed.data);
+    drop(borrowed);
+    drop(owned);
+    assert_eq!(vec![2], *finalized.borrow());
+
+    let boxed: RefOrBox<dyn MyTrait> = RefOrBox::Owned(Box::new(Implementor::default()));
+    let detached = OnDropOwned::new(boxed, |_| finalized.borrow_mut().push(3)).into_inner();
+    drop(detached);
+    assert_eq!(vec![2], *finalized.borrow());
+}
+
+//
+// Reborrow derive
+//
+
+#[cfg(feature = "derive")]
+#[derive(crate::Reborrow)]
+struct Aggregate<'t, T: Clone> {
+    name: RefOrOwned<'t, String>,
+    label: RefOrBox<'t, str>,
+    extra: T
+}
+
+#[cfg(feature = "derive")]
+#[derive(crate::Reborrow)]
+struct TupleAggregate<'t>(RefOrBox<'t, [u8]>, u8);
+
+#[test]
+#[cfg(feature = "derive")]
+fn derive_reborrow() {
+    let aggregate = Aggregate {
+        name: RefOrOwned::Owned(String::from("name")),
+        label: RefOrBox::Owned(Box::from("label")),
+        extra: 3
+    };
+    for _ in 0..2 {
+        let view = aggregate.reborrow();
+        assert!(matches!(view.name, RefOrOwned::Borrowed(_)));
+        assert!(matches!(view.label, RefOrBox::Borrowed("label")));
+        assert_eq!("name", *view.name);
+        assert_eq!(3, view.extra);
+    }
+
+    let tuple = TupleAggregate(RefOrBox::Owned(Box::new([1, 2])), 4);
+    let view = tuple.reborrow();
+    assert!(matches!(view.0, RefOrBox::Borrowed(&[1, 2])));
+    assert_eq!(4, view.1);
+}
@@ -0,0 +1,113 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefOrBox};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::net::{TcpListener, TcpStream, UdpSocket};
+
+/// A value which can only be duplicated fallibly, such as a file handle or a pooled
+/// connection.
+///
+/// This enables `try_clone_into_owned` on the wrappers, so that promotion failures
+/// surface as errors.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{RefOrOwned, TryClone, TryCloneError};
+/// struct Connection {
+///     id: u32
+/// }
+///
+/// impl TryClone for Connection {
+///     fn try_clone(&self) -> Result<Self, TryCloneError> {
+///         if self.id == 0 {
+///             return Err(TryCloneError::new("Connection pool exhausted"));
+///         }
+///         Ok(Connection { id: self.id })
+///     }
+/// }
+///
+/// let connection = Connection { id: 0 };
+/// assert!(RefOrOwned::Borrowed(&connection).try_clone_into_owned().is_err());
+/// ```
+pub trait TryClone: Sized {
+    /// Attempts to duplicate the value
+    fn try_clone(&self) -> Result<Self, TryCloneError>;
+}
+
+/// The error returned when a `TryClone` implementation fails to duplicate a value.
+#[derive(Debug)]
+pub struct TryCloneError {
+    cause: Box<dyn Error + Send + Sync>
+}
+
+impl TryCloneError {
+    /// Creates the error from its underlying cause
+    pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(cause: E) -> Self {
+        Self { cause: cause.into() }
+    }
+}
+
+impl Display for TryCloneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to clone value: {}", self.cause)
+    }
+}
+
+impl Error for TryCloneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.cause.as_ref())
+    }
+}
+
+macro_rules! io_try_clone_impls {
+    ($($handle:ty),*) => {
+        $(
+        impl TryClone for $handle {
+            fn try_clone(&self) -> Result<Self, TryCloneError> {
+                <$handle>::try_clone(self).map_err(TryCloneError::new)
+            }
+        }
+        )*
+    }
+}
+
+io_try_clone_impls!(File, TcpStream, TcpListener, UdpSocket);
+
+impl<T: TryClone> RefOrOwned<'_, T> {
+    /// Obtains an owned value of T, duplicating a borrowed value with `TryClone`.
+    ///
+    /// If the data is owned, the owned value is moved out and this never fails.
+    pub fn try_clone_into_owned(self) -> Result<T, TryCloneError> {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.try_clone(),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+}
+
+impl<T: TryClone> RefOrBox<'_, T> {
+    /// Obtains an owned box of T, duplicating a borrowed value with `TryClone`.
+    ///
+    /// If the data is owned, the owned box is moved out and this never fails.
+    pub fn try_clone_into_owned(self) -> Result<Box<T>, TryCloneError> {
+        match self {
+            Self::Borrowed(borrowed_value) => borrowed_value.try_clone().map(Box::new),
+            Self::Owned(owned_box) => Ok(owned_box)
+        }
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{RefOrOwned, RefMutOrOwned, RefOrBox, RefMutOrBox};
+
+/// The variant held by one of the wrapper types, as returned by their `kind` method.
+///
+/// This lets generic code branch on whether a value is borrowed or owned, without
+/// having to destructure the wrapper.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{Kind, RefOrBox, RefOrOwned};
+/// let value = 5;
+/// assert_eq!(Kind::Borrowed, RefOrOwned::Borrowed(&value).kind());
+///
+/// let owned: RefOrBox<str> = RefOrBox::Owned(Box::from("owned"));
+/// assert_eq!(Kind::Owned, owned.kind());
+/// assert!(owned.is_owned());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Borrowed,
+    Owned
+}
+
+/// Common access to the variants of the wrapper types, for code which is generic
+/// over them.
+///
+/// The Reference type is held by the Borrowed variant, and the Value type is held
+/// by the Owned variant.
+pub trait IntoVariant {
+    /// The reference held by the Borrowed variant
+    type Reference;
+    /// The value held by the Owned variant
+    type Value;
+
+    /// Moves out the owned value, or yields the borrowed reference as the error
+    fn into_variant(self) -> Result<Self::Value, Self::Reference>;
+}
+
+impl<'t, T> RefOrOwned<'t, T> {
+    /// Moves out the owned value without requiring `Clone`, or yields the borrowed
+    /// reference as the error.
+    ///
+    /// ```rust
+    /// # use polymorph::ref_or_owned::RefOrOwned;
+    /// let owned: RefOrOwned<String> = RefOrOwned::Owned(String::from("owned"));
+    /// assert_eq!(Ok(String::from("owned")), owned.try_into_owned());
+    ///
+    /// let shared = String::from("shared");
+    /// assert_eq!(Err(&shared), RefOrOwned::Borrowed(&shared).try_into_owned());
+    /// ```
+    pub fn try_into_owned(self) -> Result<T, &'t T> {
+        match self {
+            Self::Borrowed(borrowed_value) => Err(borrowed_value),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+}
+
+impl<'t, T> IntoVariant for RefOrOwned<'t, T> {
+    type Reference = &'t T;
+    type Value = T;
+
+    #[inline]
+    fn into_variant(self) -> Result<Self::Value, Self::Reference> {
+        self.try_into_owned()
+    }
+}
+
+impl<'t, T> RefMutOrOwned<'t, T> {
+    /// Moves out the owned value without requiring `Clone`, or yields the borrowed
+    /// reference as the error.
+    pub fn try_into_owned(self) -> Result<T, &'t mut T> {
+        match self {
+            Self::Borrowed(borrowed_value) => Err(borrowed_value),
+            Self::Owned(owned_value) => Ok(owned_value)
+        }
+    }
+}
+
+impl<'t, T> IntoVariant for RefMutOrOwned<'t, T> {
+    type Reference = &'t mut T;
+    type Value = T;
+
+    #[inline]
+    fn into_variant(self) -> Result<Self::Value, Self::Reference> {
+        self.try_into_owned()
+    }
+}
+
+impl<'t, T: ?Sized> RefOrBox<'t, T> {
+    /// Moves out the owned value without requiring `Clone`, or yields the borrowed
+    /// reference as the error.
+    pub fn try_into_owned(self) -> Result<Box<T>, &'t T> {
+        match self {
+            Self::Borrowed(borrowed_value) => Err(borrowed_value),
+            Self::Owned(owned_box) => Ok(owned_box)
+        }
+    }
+}
+
+impl<'t, T: ?Sized> IntoVariant for RefOrBox<'t, T> {
+    type Reference = &'t T;
+    type Value = Box<T>;
+
+    #[inline]
+    fn into_variant(self) -> Result<Self::Value, Self::Reference> {
+        self.try_into_owned()
+    }
+}
+
+impl<'t, T: ?Sized> RefMutOrBox<'t, T> {
+    /// Moves out the owned value without requiring `Clone`, or yields the borrowed
+    /// reference as the error.
+    pub fn try_into_owned(self) -> Result<Box<T>, &'t mut T> {
+        match self {
+            Self::Borrowed(borrowed_value) => Err(borrowed_value),
+            Self::Owned(owned_box) => Ok(owned_box)
+        }
+    }
+}
+
+impl<'t, T: ?Sized> IntoVariant for RefMutOrBox<'t, T> {
+    type Reference = &'t mut T;
+    type Value = Box<T>;
+
+    #[inline]
+    fn into_variant(self) -> Result<Self::Value, Self::Reference> {
+        self.try_into_owned()
+    }
+}
+
+/// The borrowed references and the owned values of wrappers of type `W`, as returned by
+/// `PartitionVariants::partition_variants`.
+pub type Partitioned<W> = (Vec<<W as IntoVariant>::Reference>, Vec<<W as IntoVariant>::Value>);
+
+/// Separates the borrowed items of an iterator of wrappers from the owned ones.
+///
+/// This is useful when the owned items need to be persisted or sent elsewhere,
+/// while the borrowed items are merely referenced.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::{PartitionVariants, RefOrOwned};
+/// let shared = String::from("shared");
+/// let items = vec![
+///     RefOrOwned::Borrowed(&shared),
+///     RefOrOwned::Owned(String::from("private"))
+/// ];
+///
+/// let (borrowed, owned) = items.into_iter().partition_variants();
+/// assert_eq!(vec![&shared], borrowed);
+/// assert_eq!(vec![String::from("private")], owned);
+/// ```
+pub trait PartitionVariants: Iterator where Self::Item: IntoVariant {
+    /// Consumes the iterator, collecting the borrowed references and the owned values
+    /// into separate vectors. The relative order of items is preserved within each vector.
+    fn partition_variants(self) -> Partitioned<Self::Item>;
+}
+
+impl<I> PartitionVariants for I where I: Iterator, I::Item: IntoVariant {
+    fn partition_variants(self) -> Partitioned<Self::Item> {
+        let mut borrowed = Vec::new();
+        let mut owned = Vec::new();
+        for item in self {
+            match item.into_variant() {
+                Ok(owned_value) => owned.push(owned_value),
+                Err(borrowed_value) => borrowed.push(borrowed_value)
+            }
+        }
+        (borrowed, owned)
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between the string and byte specializations of `RefOrBox`
+//! and JavaScript values. This requires the "wasm" feature.
+//!
+//! Values crossing into Rust become the Owned variant, since JavaScript memory
+//! cannot be borrowed. Values crossing out of Rust are copied into JavaScript
+//! memory, whichever variant they are.
+
+use super::RefOrBox;
+use std::ops::Deref;
+use wasm_bindgen::JsValue;
+use js_sys::{JsString, Uint8Array};
+
+impl From<RefOrBox<'_, str>> for JsValue {
+    fn from(value: RefOrBox<'_, str>) -> Self {
+        JsValue::from_str(value.deref())
+    }
+}
+
+impl From<RefOrBox<'_, str>> for JsString {
+    fn from(value: RefOrBox<'_, str>) -> Self {
+        JsString::from(value.deref())
+    }
+}
+
+impl From<JsString> for RefOrBox<'_, str> {
+    fn from(value: JsString) -> Self {
+        Self::Owned(String::from(value).into_boxed_str())
+    }
+}
+
+/// Yields the Owned variant if the value is a JavaScript string. Otherwise,
+/// the original value is returned as the error.
+impl TryFrom<JsValue> for RefOrBox<'_, str> {
+    type Error = JsValue;
+
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        match value.as_string() {
+            Some(string) => Ok(Self::Owned(string.into_boxed_str())),
+            None => Err(value)
+        }
+    }
+}
+
+impl From<RefOrBox<'_, [u8]>> for Uint8Array {
+    fn from(value: RefOrBox<'_, [u8]>) -> Self {
+        Uint8Array::from(value.deref())
+    }
+}
+
+impl From<RefOrBox<'_, [u8]>> for JsValue {
+    fn from(value: RefOrBox<'_, [u8]>) -> Self {
+        Uint8Array::from(value).into()
+    }
+}
+
+impl From<Uint8Array> for RefOrBox<'_, [u8]> {
+    fn from(value: Uint8Array) -> Self {
+        Self::Owned(value.to_vec().into_boxed_slice())
+    }
+}
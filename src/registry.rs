@@ -0,0 +1,178 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, Error, MapAccess, Visitor};
+use crate::ref_or_owned::RefOrBox;
+
+type Constructor<T> = Box<dyn for<'de> Fn(&mut dyn erased_serde::Deserializer<'de>) -> Result<Box<T>, erased_serde::Error> + Send + Sync>;
+
+/// A table of type tags and the constructors which deserialize the tagged types into
+/// trait objects.
+///
+/// Tagged values are externally tagged, as a map with a single entry from the tag to the
+/// value, as in `{"circle": {"radius": 2}}`. This is the default format of serde enums and
+/// of typetag. The registry itself is a `DeserializeSeed` for such values, and
+/// `RefOrBox::deserialize_tagged` deserializes them into the Owned variant.
+///
+/// Each plugin registers its types explicitly, so that no dispatch table need be written
+/// by hand.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// # use polymorph::registry::Registry;
+/// trait Shape {
+///     fn area(&self) -> u32;
+/// }
+///
+/// struct Square(u32);
+///
+/// impl Shape for Square {
+///     fn area(&self) -> u32 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// let mut registry: Registry<dyn Shape> = Registry::new();
+/// registry.register("square", |side: u32| Box::new(Square(side)));
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"square": 3}"#);
+/// let shape = RefOrBox::deserialize_tagged(&mut deserializer, &registry).unwrap();
+/// assert_eq!(9, shape.area());
+/// ```
+pub struct Registry<T: ?Sized> {
+    constructors: HashMap<&'static str, Constructor<T>>
+}
+
+impl<T: ?Sized> Registry<T> {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self {
+            constructors: HashMap::new()
+        }
+    }
+
+    /// Registers a type under the given tag. The type is deserialized, then turned into
+    /// a trait object with `into_box`. Returns false, keeping the existing registration,
+    /// if the tag is already taken.
+    pub fn register<U, F>(&mut self, tag: &'static str, into_box: F) -> bool
+        where U: DeserializeOwned, F: Fn(U) -> Box<T> + Send + Sync + 'static {
+
+        if self.constructors.contains_key(tag) {
+            return false;
+        }
+        let constructor: Constructor<T> = Box::new(move |deserializer| {
+            erased_serde::deserialize::<U>(deserializer).map(&into_box)
+        });
+        self.constructors.insert(tag, constructor);
+        true
+    }
+
+    /// Whether a type is registered under the given tag
+    pub fn contains(&self, tag: &str) -> bool {
+        self.constructors.contains_key(tag)
+    }
+
+    /// The registered tags, in no particular order
+    pub fn tags(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.constructors.keys().copied()
+    }
+}
+
+impl<T: ?Sized> RefOrBox<'_, T> {
+    /// Deserializes a tagged value, such as `{"tag": value}`, by looking up the tag in
+    /// the registry. The result is always the Owned variant.
+    pub fn deserialize_tagged<'de, D: Deserializer<'de>>(deserializer: D, registry: &Registry<T>) -> Result<Self, D::Error> {
+        registry.deserialize(deserializer).map(Self::Owned)
+    }
+}
+
+impl<T: ?Sized> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.constructors.keys()).finish()
+    }
+}
+
+impl<'de, T: ?Sized> DeserializeSeed<'de> for &Registry<T> {
+    type Value = Box<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(TaggedVisitor(self))
+    }
+}
+
+struct TaggedVisitor<'r, T: ?Sized>(&'r Registry<T>);
+
+impl<'de, T: ?Sized> Visitor<'de> for TaggedVisitor<'_, T> {
+    type Value = Box<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map from a registered tag to its value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let tag: String = match map.next_key()? {
+            Some(tag) => tag,
+            None => return Err(A::Error::custom("missing tag"))
+        };
+        let constructor = match self.0.constructors.get(tag.as_str()) {
+            Some(constructor) => constructor,
+            None => return Err(unknown_tag(&tag, self.0))
+        };
+        let value = map.next_value_seed(ConstructorSeed(constructor))?;
+        match map.next_key::<String>()? {
+            Some(_) => Err(A::Error::custom("expected a single tag")),
+            None => Ok(value)
+        }
+    }
+}
+
+// Error::unknown_variant needs the expected tags as a static slice, which cannot be
+// built from the registry, so the same message is formatted here
+fn unknown_tag<E: Error, T: ?Sized>(tag: &str, registry: &Registry<T>) -> E {
+    let mut expected: Vec<&str> = registry.tags().collect();
+    expected.sort_unstable();
+    match expected.as_slice() {
+        [] => E::custom(format_args!("unknown variant `{}`, there are no variants", tag)),
+        [only] => E::custom(format_args!("unknown variant `{}`, expected `{}`", tag, only)),
+        _ => {
+            let expected = expected.iter().map(|tag| format!("`{}`", tag)).collect::<Vec<_>>().join(", ");
+            E::custom(format_args!("unknown variant `{}`, expected one of {}", tag, expected))
+        }
+    }
+}
+
+struct ConstructorSeed<'c, T: ?Sized>(&'c Constructor<T>);
+
+impl<'de, T: ?Sized> DeserializeSeed<'de> for ConstructorSeed<'_, T> {
+    type Value = Box<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut erased).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[path = "registry_tests.rs"]
+mod registry_tests;
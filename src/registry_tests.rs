@@ -0,0 +1,80 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::registry::*;
+use crate::ref_or_owned::RefOrBox;
+
+trait Plugin {
+    fn describe(&self) -> String;
+}
+
+struct Counter(u32);
+
+impl Plugin for Counter {
+    fn describe(&self) -> String {
+        format!("counter at {}", self.0)
+    }
+}
+
+struct Greeter(String);
+
+impl Plugin for Greeter {
+    fn describe(&self) -> String {
+        format!("hello, {}", self.0)
+    }
+}
+
+fn registry() -> Registry<dyn Plugin> {
+    let mut registry: Registry<dyn Plugin> = Registry::new();
+    assert!(registry.register("counter", |count: u32| Box::new(Counter(count))));
+    assert!(registry.register("greeter", |name: String| Box::new(Greeter(name))));
+    registry
+}
+
+#[test]
+fn deserialize_registered_tags() {
+    let registry = registry();
+    let plugins: Vec<String> = ["{\"counter\": 4}", "{\"greeter\": \"world\"}"].iter()
+        .map(|json| RefOrBox::deserialize_tagged(&mut serde_json::Deserializer::from_str(json), &registry).unwrap())
+        .map(|plugin| plugin.describe())
+        .collect();
+    assert_eq!(vec!["counter at 4", "hello, world"], plugins);
+}
+
+#[test]
+fn reject_unknown_or_malformed() {
+    let registry = registry();
+    for json in ["{\"timer\": 1}", "{}", "{\"counter\": 1, \"greeter\": \"\"}", "{\"counter\": \"text\"}"] {
+        assert!(RefOrBox::deserialize_tagged(&mut serde_json::Deserializer::from_str(json), &registry).is_err(), "{}", json);
+    }
+}
+
+#[test]
+fn unknown_tag_lists_registered_tags() {
+    let registry = registry();
+    let error = RefOrBox::deserialize_tagged(&mut serde_json::Deserializer::from_str("{\"timer\": 1}"), &registry).err().unwrap();
+    assert!(error.to_string().starts_with("unknown variant `timer`, expected one of `counter`, `greeter`"), "{}", error);
+}
+
+#[test]
+fn tags_are_registered_once() {
+    let mut registry = registry();
+    assert!(!registry.register("counter", |count: u32| Box::new(Counter(count + 1))));
+    assert!(registry.contains("greeter"));
+    let mut tags: Vec<_> = registry.tags().collect();
+    tags.sort_unstable();
+    assert_eq!(vec!["counter", "greeter"], tags);
+}
@@ -0,0 +1,400 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Functions for use with `#[serde(with = "...")]`, for fields which the `Serialize`
+//! and `Deserialize` implementations of the wrappers do not cover. Seeds for stateful
+//! deserialization of the wrappers are also provided.
+//!
+//! Each submodule contains a `serialize` and a `deserialize` function. The value is
+//! serialized as the wrapped value would be, and is always deserialized as the Owned
+//! variant. Unlike the borrowing `Deserialize` implementations, the helpers can
+//! therefore be used with `DeserializeOwned` and with readers.
+//!
+//! ```rust
+//! use polymorph::ref_or_owned::{RefOrBox, RefOrOwned};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Header {
+//!     id: u32
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Message<'t> {
+//!     #[serde(with = "polymorph::serde_helpers::ref_or_box_str")]
+//!     body: RefOrBox<'t, str>,
+//!     #[serde(with = "polymorph::serde_helpers::ref_or_owned")]
+//!     header: RefOrOwned<'t, Header>
+//! }
+//!
+//! let input = r#"{"body":"hello","header":{"id":1}}"#;
+//! let message: Message<'static> = serde_json::from_reader(input.as_bytes()).unwrap();
+//! assert!(message.body.is_owned());
+//! assert_eq!(1, message.header.id);
+//! assert_eq!(input, serde_json::to_string(&message).unwrap());
+//! ```
+
+use std::ffi::{OsStr, OsString};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::DeserializeSeed;
+use crate::ref_or_owned::{RefMutOrBox, RefMutOrOwned, RefOrBox, RefOrOwned};
+
+/// Helpers for `RefOrOwned<T>`, always deserializing the Owned variant
+pub mod ref_or_owned {
+    use super::*;
+
+    /// Serializes the wrapped value
+    pub fn serialize<T: Serialize, S: Serializer>(value: &RefOrOwned<'_, T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.deref().serialize(serializer)
+    }
+
+    /// Deserializes a `T` as the Owned variant
+    pub fn deserialize<'de, 't, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrOwned<'t, T>, D::Error> {
+        T::deserialize(deserializer).map(RefOrOwned::Owned)
+    }
+}
+
+/// Helpers for `RefOrBox<T>`, always deserializing the Owned variant.
+///
+/// Any `T` whose box can be deserialized is supported. This includes trait objects
+/// whose trait is annotated with `#[typetag::serde]`, so that `RefOrBox<dyn MyTrait>`
+/// fields can be deserialized. Serializing them needs no helper.
+///
+/// ```rust
+/// use polymorph::ref_or_owned::RefOrBox;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[typetag::serde]
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Square(f64);
+///
+/// #[typetag::serde]
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Drawing<'t> {
+///     #[serde(with = "polymorph::serde_helpers::ref_or_box")]
+///     shape: RefOrBox<'t, dyn Shape>
+/// }
+///
+/// let square = Square(2.0);
+/// let json = serde_json::to_string(&Drawing { shape: RefOrBox::Borrowed(&square) }).unwrap();
+/// let drawing: Drawing = serde_json::from_str(&json).unwrap();
+/// assert!(drawing.shape.is_owned());
+/// assert_eq!(4.0, drawing.shape.area());
+/// ```
+///
+/// Where the trait objects are built by a factory of your own, such as from a
+/// description of the value, use `deserialize_with_factory` in a function for
+/// `#[serde(deserialize_with = "...")]`:
+///
+/// ```rust
+/// use polymorph::ref_or_owned::RefOrBox;
+/// use polymorph::serde_helpers::ref_or_box;
+/// use serde::{Deserialize, Deserializer};
+///
+/// trait Greeter {
+///     fn greet(&self) -> String;
+/// }
+///
+/// struct Formal;
+/// struct Casual;
+/// impl Greeter for Formal {
+///     fn greet(&self) -> String { String::from("Good day") }
+/// }
+/// impl Greeter for Casual {
+///     fn greet(&self) -> String { String::from("Hi") }
+/// }
+///
+/// fn greeter<'de, 't, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, dyn Greeter>, D::Error> {
+///     ref_or_box::deserialize_with_factory(deserializer, |formal: bool| -> Box<dyn Greeter> {
+///         if formal { Box::new(Formal) } else { Box::new(Casual) }
+///     })
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct Settings<'t> {
+///     #[serde(deserialize_with = "greeter")]
+///     greeter: RefOrBox<'t, dyn Greeter>
+/// }
+///
+/// let settings: Settings = serde_json::from_str(r#"{"greeter":true}"#).unwrap();
+/// assert_eq!("Good day", settings.greeter.greet());
+/// ```
+pub mod ref_or_box {
+    use super::*;
+
+    /// Serializes the wrapped value
+    pub fn serialize<T: ?Sized + Serialize, S: Serializer>(value: &RefOrBox<'_, T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.deref().serialize(serializer)
+    }
+
+    /// Deserializes a `Box<T>`, as the Owned variant
+    pub fn deserialize<'de, 't, T: ?Sized, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, T>, D::Error>
+        where Box<T>: Deserialize<'de> {
+
+        Box::<T>::deserialize(deserializer).map(RefOrBox::Owned)
+    }
+
+    /// Deserializes a `U`, and builds the Owned variant from it using the factory
+    pub fn deserialize_with_factory<'de, 't, T, U, D, F>(deserializer: D, factory: F) -> Result<RefOrBox<'t, T>, D::Error>
+        where T: ?Sized, U: Deserialize<'de>, D: Deserializer<'de>, F: FnOnce(U) -> Box<T> {
+
+        U::deserialize(deserializer).map(|description| RefOrBox::Owned(factory(description)))
+    }
+}
+
+/// Helpers for `RefOrBox<str>`, always deserializing the Owned variant
+pub mod ref_or_box_str {
+    use super::*;
+
+    /// Serializes the wrapped string
+    pub fn serialize<S: Serializer>(value: &RefOrBox<'_, str>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserializes a string into a new box, as the Owned variant
+    pub fn deserialize<'de, 't, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, str>, D::Error> {
+        Box::<str>::deserialize(deserializer).map(RefOrBox::Owned)
+    }
+}
+
+/// Helpers for `RefOrBox<[u8]>`, always deserializing the Owned variant
+pub mod ref_or_box_bytes {
+    use super::*;
+
+    /// Serializes the wrapped bytes as a byte array
+    pub fn serialize<S: Serializer>(value: &RefOrBox<'_, [u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(value)
+    }
+
+    /// Deserializes bytes into a new box, as the Owned variant
+    pub fn deserialize<'de, 't, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, [u8]>, D::Error> {
+        Ok(match RefOrBox::<[u8]>::deserialize(deserializer)? {
+            RefOrBox::Borrowed(bytes) => RefOrBox::Owned(Box::from(bytes)),
+            RefOrBox::Owned(bytes) => RefOrBox::Owned(bytes)
+        })
+    }
+}
+
+/// Helpers for `RefOrBox<OsStr>`, always deserializing the Owned variant.
+///
+/// The OS string is serialized as a string if it is valid UTF-8. Otherwise, on Unix, its
+/// raw bytes are serialized instead, where serde's own `Serialize` for `OsStr` would be
+/// platform-specific. Other platforms report an error for such values. Both strings and
+/// bytes are accepted when deserializing.
+pub mod ref_or_box_os_str {
+    use super::*;
+
+    /// Serializes the wrapped OS string as a string, or as bytes if it is not UTF-8
+    pub fn serialize<S: Serializer>(value: &RefOrBox<'_, OsStr>, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_os_str(value, serializer)
+    }
+
+    /// Deserializes a string or bytes into a new box, as the Owned variant
+    pub fn deserialize<'de, 't, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, OsStr>, D::Error> {
+        deserialize_os_string(deserializer).map(|value| RefOrBox::Owned(value.into_boxed_os_str()))
+    }
+}
+
+/// Helpers for `RefOrBox<Path>`, always deserializing the Owned variant.
+///
+/// Paths fall back to bytes in the same way as `ref_or_box_os_str`, so that non-UTF-8
+/// paths can be serialized on Unix.
+///
+/// ```rust
+/// use polymorph::ref_or_owned::RefOrBox;
+/// use serde::{Deserialize, Serialize};
+/// use std::path::Path;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Config<'t> {
+///     #[serde(with = "polymorph::serde_helpers::ref_or_box_path")]
+///     root: RefOrBox<'t, Path>
+/// }
+///
+/// let json = serde_json::to_string(&Config { root: RefOrBox::Borrowed(Path::new("/srv")) }).unwrap();
+/// assert_eq!(r#"{"root":"/srv"}"#, json);
+/// let config: Config = serde_json::from_str(&json).unwrap();
+/// assert_eq!(Path::new("/srv"), &*config.root);
+/// ```
+pub mod ref_or_box_path {
+    use super::*;
+
+    /// Serializes the wrapped path as a string, or as bytes if it is not UTF-8
+    pub fn serialize<S: Serializer>(value: &RefOrBox<'_, Path>, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_os_str(value.as_os_str(), serializer)
+    }
+
+    /// Deserializes a string or bytes into a new box, as the Owned variant
+    pub fn deserialize<'de, 't, D: Deserializer<'de>>(deserializer: D) -> Result<RefOrBox<'t, Path>, D::Error> {
+        deserialize_os_string(deserializer).map(|value| RefOrBox::Owned(PathBuf::from(value).into_boxed_path()))
+    }
+}
+
+fn serialize_os_str<S: Serializer>(value: &OsStr, serializer: S) -> Result<S::Ok, S::Error> {
+    if let Some(text) = value.to_str() {
+        return serializer.serialize_str(text);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        serializer.serialize_bytes(value.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        Err(serde::ser::Error::custom("path contains invalid UTF-8 characters"))
+    }
+}
+
+fn deserialize_os_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OsString, D::Error> {
+    let bytes = match RefOrBox::<[u8]>::deserialize(deserializer)? {
+        RefOrBox::Borrowed(bytes) => bytes.to_vec(),
+        RefOrBox::Owned(bytes) => bytes.into_vec()
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        Ok(OsString::from_vec(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(bytes).map(OsString::from).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Helpers for `RefOrBox<T>` where `T` is a trait object serialized through erased-serde.
+/// This requires the "erased-serde" feature.
+///
+/// Use with `#[serde(serialize_with = "polymorph::serde_helpers::erased::serialize")]`.
+/// Deserialization is not supported, as erased-serde cannot tell which type to create.
+#[cfg(feature = "erased-serde")]
+pub mod erased {
+    use super::*;
+
+    /// Serializes the wrapped value through `erased_serde::Serialize`
+    pub fn serialize<T: ?Sized + erased_serde::Serialize, S: Serializer>(value: &RefOrBox<'_, T>, serializer: S) -> Result<S::Ok, S::Error> {
+        erased_serde::serialize(value.deref(), serializer)
+    }
+}
+
+macro_rules! seed_impls {
+    ($seed:ident, $typename:ident, [$($unsized:tt)*]) => {
+        impl<'t, T: $($unsized)*, S> $seed<'t, T, S> {
+            /// Wraps the seed
+            pub fn new(seed: S) -> Self {
+                Self {
+                    seed,
+                    marker: PhantomData
+                }
+            }
+
+            /// Yields the wrapped seed
+            pub fn into_inner(self) -> S {
+                self.seed
+            }
+        }
+
+        impl<'de, 't, T: $($unsized)*, S> DeserializeSeed<'de> for $seed<'t, T, S>
+            where S: DeserializeSeed<'de>, S::Value: Into<$typename<'t, T>> {
+
+            type Value = $typename<'t, T>;
+
+            fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                self.seed.deserialize(deserializer).map(Into::into)
+            }
+        }
+    }
+}
+
+/// A `DeserializeSeed` producing a `RefOrOwned`, by means of another seed.
+///
+/// The wrapped seed carries user state, such as an arena, interner, or registry, into
+/// deserialization. It may yield either a `T`, which becomes the Owned variant, or a
+/// `&T`, which becomes the Borrowed variant. Thus, values looked up in a registry or
+/// allocated in an arena can be deserialized without being copied.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RefOrOwned;
+/// # use polymorph::serde_helpers::RefOrOwnedSeed;
+/// use serde::de::{DeserializeSeed, Deserializer};
+/// use serde::Deserialize;
+///
+/// struct Lookup<'r>(&'r [String]);
+///
+/// impl<'de, 'r> DeserializeSeed<'de> for Lookup<'r> {
+///     type Value = &'r String;
+///
+///     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+///         let index = usize::deserialize(deserializer)?;
+///         self.0.get(index).ok_or_else(|| serde::de::Error::custom("no such entry"))
+///     }
+/// }
+///
+/// let registry = vec![String::from("first"), String::from("second")];
+/// let seed = RefOrOwnedSeed::new(Lookup(&registry));
+/// let value: RefOrOwned<String> = seed.deserialize(&mut serde_json::Deserializer::from_str("1")).unwrap();
+/// assert!(value.is_borrowed());
+/// assert_eq!("second", *value);
+/// ```
+pub struct RefOrOwnedSeed<'t, T, S> {
+    seed: S,
+    marker: PhantomData<fn() -> RefOrOwned<'t, T>>
+}
+
+seed_impls!(RefOrOwnedSeed, RefOrOwned, []);
+
+/// A `DeserializeSeed` producing a `RefMutOrOwned`, by means of another seed yielding
+/// either a `T` or a `&mut T`. See `RefOrOwnedSeed`.
+pub struct RefMutOrOwnedSeed<'t, T, S> {
+    seed: S,
+    marker: PhantomData<fn() -> RefMutOrOwned<'t, T>>
+}
+
+seed_impls!(RefMutOrOwnedSeed, RefMutOrOwned, []);
+
+/// A `DeserializeSeed` producing a `RefOrBox`, by means of another seed yielding
+/// either a `Box<T>` or a `&T`. See `RefOrOwnedSeed`.
+pub struct RefOrBoxSeed<'t, T: ?Sized, S> {
+    seed: S,
+    marker: PhantomData<fn() -> RefOrBox<'t, T>>
+}
+
+seed_impls!(RefOrBoxSeed, RefOrBox, [?Sized]);
+
+/// A `DeserializeSeed` producing a `RefMutOrBox`, by means of another seed yielding
+/// either a `Box<T>` or a `&mut T`. See `RefOrOwnedSeed`.
+pub struct RefMutOrBoxSeed<'t, T: ?Sized, S> {
+    seed: S,
+    marker: PhantomData<fn() -> RefMutOrBox<'t, T>>
+}
+
+seed_impls!(RefMutOrBoxSeed, RefMutOrBox, [?Sized]);
+
+#[cfg(test)]
+#[path = "serde_helpers_tests.rs"]
+mod serde_helpers_tests;
@@ -0,0 +1,172 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::serde_helpers::*;
+use crate::ref_or_owned::{RefMutOrOwned, RefOrBox, RefOrOwned};
+use serde::de::{DeserializeSeed, Deserializer};
+use serde::Deserialize;
+use std::marker::PhantomData;
+
+fn to_json<F: FnOnce(&mut serde_json::Serializer<&mut Vec<u8>>) -> serde_json::Result<()>>(serialize: F) -> String {
+    let mut output = Vec::new();
+    serialize(&mut serde_json::Serializer::new(&mut output)).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn serialize_wrapped_value() {
+    let header = vec![1, 2];
+    assert_eq!("[1,2]", to_json(|ser| ref_or_owned::serialize(&RefOrOwned::Borrowed(&header), ser)));
+    assert_eq!("3", to_json(|ser| ref_or_box::serialize(&RefOrBox::Owned(Box::new(3)), ser)));
+    assert_eq!("\"body\"", to_json(|ser| ref_or_box_str::serialize(&RefOrBox::Borrowed("body"), ser)));
+    assert_eq!("[4,5]", to_json(|ser| ref_or_box_bytes::serialize(&RefOrBox::Borrowed(&[4, 5][..]), ser)));
+}
+
+#[test]
+fn deserialize_always_owned() {
+    let header: RefOrOwned<Vec<u8>> = ref_or_owned::deserialize(&mut serde_json::Deserializer::from_str("[1,2]")).unwrap();
+    assert!(header.is_owned());
+    assert_eq!(vec![1, 2], *header);
+
+    let boxed: RefOrBox<u32> = ref_or_box::deserialize(&mut serde_json::Deserializer::from_str("3")).unwrap();
+    assert!(boxed.is_owned());
+    assert_eq!(3, *boxed);
+
+    let body: RefOrBox<'static, str> = ref_or_box_str::deserialize(&mut serde_json::Deserializer::from_reader("\"body\"".as_bytes())).unwrap();
+    assert!(body.is_owned());
+    assert_eq!("body", &*body);
+
+    let bytes: RefOrBox<[u8]> = ref_or_box_bytes::deserialize(&mut serde_json::Deserializer::from_str("[4,5]")).unwrap();
+    assert!(bytes.is_owned());
+    assert_eq!([4, 5], *bytes);
+}
+
+#[test]
+fn path_round_trip() {
+    use std::path::Path;
+    let json = to_json(|ser| ref_or_box_path::serialize(&RefOrBox::Borrowed(Path::new("/srv/data")), ser));
+    assert_eq!("\"/srv/data\"", json);
+    let path = ref_or_box_path::deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+    assert!(path.is_owned());
+    assert_eq!(Path::new("/srv/data"), &*path);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_os_str_falls_back_to_bytes() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    let name = OsStr::from_bytes(b"file\xff");
+    let json = to_json(|ser| ref_or_box_os_str::serialize(&RefOrBox::Borrowed(name), ser));
+    assert_eq!("[102,105,108,101,255]", json);
+    let decoded = ref_or_box_os_str::deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+    assert_eq!(name, &*decoded);
+
+    let json = to_json(|ser| ref_or_box_path::serialize(&RefOrBox::Borrowed(Path::new(name)), ser));
+    let path = ref_or_box_path::deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+    assert_eq!(Path::new(name), &*path);
+}
+
+struct Arena<'a>(&'a mut Vec<u32>);
+
+impl<'de, 'a> DeserializeSeed<'de> for Arena<'a> {
+    type Value = &'a mut u32;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.push(u32::deserialize(deserializer)?);
+        Ok(self.0.last_mut().unwrap())
+    }
+}
+
+#[test]
+fn seed_threads_state() {
+    let mut arena = Vec::new();
+    let seed = RefMutOrOwnedSeed::new(Arena(&mut arena));
+    let mut value: RefMutOrOwned<u32> = seed.deserialize(&mut serde_json::Deserializer::from_str("7")).unwrap();
+    assert!(value.is_borrowed());
+    *value += 1;
+    assert_eq!(vec![8], arena);
+}
+
+#[test]
+fn seed_yields_owned() {
+    let seed = RefOrBoxSeed::new(PhantomData::<Box<str>>);
+    let value: RefOrBox<str> = seed.deserialize(&mut serde_json::Deserializer::from_str("\"text\"")).unwrap();
+    assert!(value.is_owned());
+    assert_eq!("text", &*value);
+
+    let seed = RefOrOwnedSeed::new(PhantomData::<u32>);
+    let value: RefOrOwned<u32> = seed.deserialize(&mut serde_json::Deserializer::from_str("3")).unwrap();
+    assert_eq!(RefOrOwned::Owned(3), value);
+}
+
+#[typetag::serde]
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+#[typetag::serde]
+impl Shape for u32 {
+    fn area(&self) -> u32 {
+        self * self
+    }
+}
+
+#[test]
+fn trait_object_round_trip() {
+    let square = 3;
+    let borrowed: RefOrBox<dyn Shape> = RefOrBox::Borrowed(&square);
+    let json = serde_json::to_string(&borrowed).unwrap();
+    assert_eq!(json, to_json(|ser| ref_or_box::serialize(&borrowed, ser)));
+
+    let deserialized: RefOrBox<dyn Shape> = ref_or_box::deserialize(&mut serde_json::Deserializer::from_str(&json)).unwrap();
+    assert!(deserialized.is_owned());
+    assert_eq!(9, deserialized.area());
+}
+
+#[test]
+fn trait_object_from_factory() {
+    let shape: RefOrBox<dyn Shape> = ref_or_box::deserialize_with_factory(
+        &mut serde_json::Deserializer::from_str("[2,3]"),
+        |[width, height]: [u32; 2]| Box::new(width * height) as Box<dyn Shape>
+    ).unwrap();
+    assert!(shape.is_owned());
+    assert_eq!(36, shape.area());
+}
+
+#[cfg(feature = "erased-serde")]
+trait Event: erased_serde::Serialize {}
+
+#[cfg(feature = "erased-serde")]
+impl Event for u32 {}
+
+#[cfg(feature = "erased-serde")]
+impl Event for String {}
+
+#[test]
+#[cfg(feature = "erased-serde")]
+fn erased_trait_objects() {
+    let first = 1_u32;
+    let events: Vec<RefOrBox<dyn Event>> = vec![
+        RefOrBox::Borrowed(&first),
+        RefOrBox::Owned(Box::new(String::from("two")))
+    ];
+    let erased: Vec<_> = events.iter().map(RefOrBox::as_erased).collect();
+    assert_eq!("[1,\"two\"]", serde_json::to_string(&erased).unwrap());
+    assert_eq!("\"two\"", to_json(|ser| erased::serialize(&events[1], ser)));
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ref_or_owned::RefOrBox;
+
+/// A state which reacts to events of type `E`.
+///
+/// Transitions may lead either to a borrowed state, typically a shared static
+/// state without any data of its own, or to an owned boxed state, which carries
+/// data specific to the current run of the machine.
+pub trait State<'t, E> {
+    /// Determines the next state upon receiving the given event.
+    ///
+    /// Returns `None` if the machine should remain in this state.
+    fn transition(&self, event: &E) -> Option<RefOrBox<'t, dyn State<'t, E> + 't>>;
+
+    /// A name for this state, used for diagnostics. Defaults to the type name.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Drives a machine of `State`s, keeping the current state as a `RefOrBox`.
+///
+/// ```rust
+/// # use polymorph::ref_or_owned::RefOrBox;
+/// # use polymorph::state::{State, StateMachine};
+/// struct Locked;
+/// struct Unlocked;
+///
+/// static LOCKED: Locked = Locked;
+/// static UNLOCKED: Unlocked = Unlocked;
+///
+/// impl State<'static, bool> for Locked {
+///     fn transition(&self, key_correct: &bool) -> Option<RefOrBox<'static, dyn State<'static, bool>>> {
+///         if *key_correct { Some(RefOrBox::Borrowed(&UNLOCKED)) } else { None }
+///     }
+///     fn name(&self) -> &str { "locked" }
+/// }
+///
+/// impl State<'static, bool> for Unlocked {
+///     fn transition(&self, _: &bool) -> Option<RefOrBox<'static, dyn State<'static, bool>>> {
+///         Some(RefOrBox::Borrowed(&LOCKED))
+///     }
+///     fn name(&self) -> &str { "unlocked" }
+/// }
+///
+/// let mut machine = StateMachine::new(&LOCKED as &dyn State<bool>);
+/// assert!(!machine.step(false));
+/// assert!(machine.step(true));
+/// assert_eq!("unlocked", machine.current().name());
+/// ```
+pub struct StateMachine<'t, E> {
+    current: RefOrBox<'t, dyn State<'t, E> + 't>
+}
+
+impl<'t, E> StateMachine<'t, E> {
+    /// Creates a machine starting in the given state
+    pub fn new<S>(initial: S) -> Self where S: Into<RefOrBox<'t, dyn State<'t, E> + 't>> {
+        Self {
+            current: initial.into()
+        }
+    }
+
+    /// Feeds an event to the current state, and moves to the next state if there is one.
+    ///
+    /// When a transition occurs, the previous state is dropped if it was owned.
+    /// Returns whether a transition occurred.
+    pub fn step(&mut self, event: E) -> bool {
+        match self.current.transition(&event) {
+            Some(next) => {
+                self.current = next;
+                true
+            },
+            None => false
+        }
+    }
+
+    /// The current state
+    pub fn current(&self) -> &RefOrBox<'t, dyn State<'t, E> + 't> {
+        &self.current
+    }
+
+    /// Stops the machine, yielding the current state
+    pub fn into_current(self) -> RefOrBox<'t, dyn State<'t, E> + 't> {
+        self.current
+    }
+}
+
+#[cfg(test)]
+#[path = "state_tests.rs"]
+mod state_tests;
@@ -0,0 +1,119 @@
+/*
+ * Copyright © 2021 Anand Beh
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::state::*;
+use crate::ref_or_owned::RefOrBox;
+use std::cell::Cell;
+
+enum Signal {
+    Tick,
+    Press
+}
+
+type Next<'t> = Option<RefOrBox<'t, dyn State<'t, Signal> + 't>>;
+
+struct Idle;
+struct Waiting {
+    remaining: u8
+}
+struct Walking;
+
+static IDLE: Idle = Idle;
+static WALKING: Walking = Walking;
+
+impl State<'static, Signal> for Idle {
+    fn transition(&self, event: &Signal) -> Next<'static> {
+        match event {
+            Signal::Press => Some(RefOrBox::Owned(Box::new(Waiting { remaining: 2 }))),
+            Signal::Tick => None
+        }
+    }
+
+    fn name(&self) -> &str {
+        "idle"
+    }
+}
+
+impl State<'static, Signal> for Waiting {
+    fn transition(&self, event: &Signal) -> Next<'static> {
+        match (event, self.remaining) {
+            (Signal::Tick, 0) => Some(RefOrBox::Borrowed(&WALKING)),
+            (Signal::Tick, remaining) => Some(RefOrBox::Owned(Box::new(Waiting { remaining: remaining - 1 }))),
+            (Signal::Press, _) => None
+        }
+    }
+
+    fn name(&self) -> &str {
+        "waiting"
+    }
+}
+
+impl State<'static, Signal> for Walking {
+    fn transition(&self, event: &Signal) -> Next<'static> {
+        match event {
+            Signal::Tick => Some(RefOrBox::Borrowed(&IDLE)),
+            Signal::Press => None
+        }
+    }
+
+    fn name(&self) -> &str {
+        "walking"
+    }
+}
+
+#[test]
+fn step_moves_between_borrowed_and_owned_states() {
+    let mut machine = StateMachine::new(&IDLE as &dyn State<Signal>);
+    assert!(!machine.step(Signal::Tick));
+    assert!(matches!(machine.current(), RefOrBox::Borrowed(_)));
+
+    assert!(machine.step(Signal::Press));
+    assert_eq!("waiting", machine.current().name());
+    assert!(matches!(machine.current(), RefOrBox::Owned(_)));
+
+    assert!(!machine.step(Signal::Press));
+    assert!(machine.step(Signal::Tick));
+    assert!(machine.step(Signal::Tick));
+    assert_eq!("waiting", machine.current().name());
+    assert!(machine.step(Signal::Tick));
+    assert_eq!("walking", machine.current().name());
+    assert!(matches!(machine.current(), RefOrBox::Borrowed(_)));
+
+    assert!(machine.step(Signal::Tick));
+    assert_eq!("idle", machine.into_current().name());
+}
+
+struct Counting<'c> {
+    steps: &'c Cell<u32>
+}
+
+impl<'c> State<'c, ()> for Counting<'c> {
+    fn transition(&self, _: &()) -> Option<RefOrBox<'c, dyn State<'c, ()> + 'c>> {
+        self.steps.set(self.steps.get() + 1);
+        Some(RefOrBox::Owned(Box::new(Counting { steps: self.steps })))
+    }
+}
+
+#[test]
+fn owned_states_may_borrow() {
+    let steps = Cell::new(0);
+    let initial: Box<dyn State<()>> = Box::new(Counting { steps: &steps });
+    let mut machine = StateMachine::new(initial);
+    machine.step(());
+    machine.step(());
+    assert_eq!(2, steps.get());
+    assert!(machine.current().name().contains("Counting"));
+}